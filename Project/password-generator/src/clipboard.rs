@@ -0,0 +1,64 @@
+use std::io::{self, Write};
+use std::process::{Command, Stdio};
+
+/// Somewhere a password can be copied to.
+pub trait Clipboard {
+    fn set_text(&mut self, text: &str) -> io::Result<()>;
+}
+
+/// Lets a borrowed clipboard be handed to `ClipboardOutput` and still be
+/// looked at afterwards.
+impl<C: Clipboard + ?Sized> Clipboard for &mut C {
+    fn set_text(&mut self, text: &str) -> io::Result<()> {
+        (**self).set_text(text)
+    }
+}
+
+/// The desktop clipboard, reached through whichever platform tool is
+/// installed.
+pub struct SystemClipboard;
+
+/// Tried in order; the first one that exists wins.
+const BACKENDS: &[(&str, &[&str])] = &[
+    ("pbcopy", &[]),
+    ("wl-copy", &[]),
+    ("xclip", &["-selection", "clipboard"]),
+    ("xsel", &["--clipboard", "--input"]),
+    ("clip.exe", &[]),
+];
+
+impl Clipboard for SystemClipboard {
+    fn set_text(&mut self, text: &str) -> io::Result<()> {
+        for (program, args) in BACKENDS {
+            let mut child = match Command::new(program)
+                .args(*args)
+                .stdin(Stdio::piped())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .spawn()
+            {
+                Ok(child) => child,
+                Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+                Err(err) => return Err(err),
+            };
+
+            if let Some(mut stdin) = child.stdin.take() {
+                stdin.write_all(text.as_bytes())?;
+            }
+
+            let status = child.wait()?;
+            return if status.success() {
+                Ok(())
+            } else {
+                Err(io::Error::other(format!(
+                    "`{program}` exited with {status}"
+                )))
+            };
+        }
+
+        Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            "no clipboard backend available",
+        ))
+    }
+}
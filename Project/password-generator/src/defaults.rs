@@ -0,0 +1,170 @@
+use crate::{CharClasses, PasswordConfig};
+use serde::Deserialize;
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// File name looked up in the user's home directory.
+pub const DEFAULTS_FILE_NAME: &str = ".password-generator.toml";
+
+/// User defaults read from `~/.password-generator.toml`, e.g.
+///
+/// ```toml
+/// length = 20
+/// classes = ["upper", "lower", "digits", "symbols"]
+/// separator = "_"
+/// ```
+///
+/// Every key is optional; command-line flags override whatever is set here.
+#[derive(Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct Defaults {
+    pub length: Option<usize>,
+    pub classes: Option<Vec<Class>>,
+    /// Passphrase word separator.
+    pub separator: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Class {
+    Upper,
+    Lower,
+    Digits,
+    Symbols,
+}
+
+#[derive(Debug)]
+pub enum DefaultsError {
+    Io(io::Error),
+    Parse(toml::de::Error),
+}
+
+impl fmt::Display for DefaultsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DefaultsError::Io(err) => write!(f, "couldn't read defaults file: {err}"),
+            DefaultsError::Parse(err) => write!(f, "invalid defaults file: {err}"),
+        }
+    }
+}
+
+impl Error for DefaultsError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            DefaultsError::Io(err) => Some(err),
+            DefaultsError::Parse(err) => Some(err),
+        }
+    }
+}
+
+impl Defaults {
+    /// `~/.password-generator.toml`, or `None` if there's no home directory.
+    pub fn path() -> Option<PathBuf> {
+        std::env::var_os("HOME")
+            .or_else(|| std::env::var_os("USERPROFILE"))
+            .map(|home| PathBuf::from(home).join(DEFAULTS_FILE_NAME))
+    }
+
+    /// Reads defaults from `path`. A missing file is not an error and yields
+    /// `Ok(None)`.
+    pub fn load(path: &Path) -> Result<Option<Defaults>, DefaultsError> {
+        let contents = match std::fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(DefaultsError::Io(err)),
+        };
+
+        toml::from_str(&contents)
+            .map(Some)
+            .map_err(DefaultsError::Parse)
+    }
+
+    /// Copies every value set in the file onto `config`.
+    pub fn apply(&self, config: &mut PasswordConfig) {
+        if let Some(length) = self.length {
+            config.length = length;
+        }
+        if let Some(classes) = &self.classes {
+            let mut enabled = CharClasses::none();
+            for class in classes {
+                match class {
+                    Class::Upper => enabled.upper = true,
+                    Class::Lower => enabled.lower = true,
+                    Class::Digits => enabled.digits = true,
+                    Class::Symbols => enabled.symbols = true,
+                }
+            }
+            config.classes = enabled;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Writes `contents` to a file of its own in the temp directory.
+    fn temp_file(name: &str, contents: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "password-generator-{}-{name}.toml",
+            std::process::id()
+        ));
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn values_from_the_file_are_applied() {
+        let path = temp_file(
+            "applied",
+            "length = 20\nclasses = [\"lower\", \"digits\"]\nseparator = \"_\"\n",
+        );
+        let defaults = Defaults::load(&path).unwrap().unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let mut config = PasswordConfig::default();
+        defaults.apply(&mut config);
+
+        assert_eq!(config.length, 20);
+        assert_eq!(
+            config.classes,
+            CharClasses {
+                upper: false,
+                lower: true,
+                digits: true,
+                symbols: false,
+            }
+        );
+        assert_eq!(defaults.separator.as_deref(), Some("_"));
+    }
+
+    #[test]
+    fn unset_keys_leave_the_config_alone() {
+        let path = temp_file("partial", "length = 16\n");
+        let defaults = Defaults::load(&path).unwrap().unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let mut config = PasswordConfig::default();
+        defaults.apply(&mut config);
+
+        assert_eq!(config.length, 16);
+        assert_eq!(config.classes, CharClasses::default());
+    }
+
+    #[test]
+    fn a_missing_file_is_not_an_error() {
+        let path = std::env::temp_dir().join("password-generator-no-such-defaults.toml");
+        assert!(matches!(Defaults::load(&path), Ok(None)));
+    }
+
+    #[test]
+    fn an_unknown_key_is_a_parse_error() {
+        let path = temp_file("unknown", "lenght = 20\n");
+        let result = Defaults::load(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(matches!(result, Err(DefaultsError::Parse(_))));
+    }
+}
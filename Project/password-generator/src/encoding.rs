@@ -0,0 +1,121 @@
+use crate::{check_length, PasswordError};
+use rand::Rng;
+
+/// RFC 4648 base32 alphabet.
+pub const BASE32_ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+/// RFC 4648 URL- and filename-safe base64 alphabet.
+pub const BASE64URL_ALPHABET: &[u8] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Token encodings for `--encoding`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    Base32,
+    Base64Url,
+}
+
+impl Encoding {
+    pub fn parse(name: &str) -> Option<Encoding> {
+        match name {
+            "base32" => Some(Encoding::Base32),
+            "base64url" => Some(Encoding::Base64Url),
+            _ => None,
+        }
+    }
+
+    pub fn alphabet(&self) -> &'static [u8] {
+        match self {
+            Encoding::Base32 => BASE32_ALPHABET,
+            Encoding::Base64Url => BASE64URL_ALPHABET,
+        }
+    }
+
+    /// Bits of input carried by each output character.
+    pub fn bits_per_char(&self) -> usize {
+        match self {
+            Encoding::Base32 => 5,
+            Encoding::Base64Url => 6,
+        }
+    }
+
+    /// Random bytes needed to fill `char_count` output characters.
+    pub fn bytes_for(&self, char_count: usize) -> usize {
+        (char_count * self.bits_per_char()).div_ceil(8)
+    }
+
+    /// Encodes `bytes` without padding. The last character takes whatever
+    /// bits are left, padded with zero bits.
+    pub fn encode(&self, bytes: &[u8]) -> String {
+        let alphabet = self.alphabet();
+        let width = self.bits_per_char();
+        let mask = (1u32 << width) - 1;
+
+        let mut output = String::new();
+        let mut buffer = 0u32;
+        let mut buffered = 0;
+        for &byte in bytes {
+            buffer = (buffer << 8) | u32::from(byte);
+            buffered += 8;
+            while buffered >= width {
+                buffered -= width;
+                output.push(char::from(alphabet[((buffer >> buffered) & mask) as usize]));
+            }
+        }
+        if buffered > 0 {
+            output.push(char::from(
+                alphabet[((buffer << (width - buffered)) & mask) as usize],
+            ));
+        }
+        output
+    }
+}
+
+/// A token of exactly `length` characters from `encoding`'s alphabet, made
+/// by encoding `encoding.bytes_for(length)` random bytes.
+pub fn generate_encoded<R: Rng + ?Sized>(
+    rng: &mut R,
+    encoding: Encoding,
+    length: usize,
+) -> Result<String, PasswordError> {
+    check_length(length)?;
+
+    let mut bytes = vec![0u8; encoding.bytes_for(length)];
+    rng.fill(&mut bytes[..]);
+
+    let mut token = encoding.encode(&bytes);
+    // Whole bytes can overshoot by a character.
+    token.truncate(length);
+    Ok(token)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn tokens_have_the_exact_length_and_stay_in_the_alphabet() {
+        let mut rng = StdRng::seed_from_u64(72);
+        for encoding in [Encoding::Base32, Encoding::Base64Url] {
+            for length in [1, 7, 8, 13, 32, 100] {
+                let token = generate_encoded(&mut rng, encoding, length).unwrap();
+
+                assert_eq!(token.len(), length);
+                assert!(
+                    token
+                        .bytes()
+                        .all(|byte| encoding.alphabet().contains(&byte)),
+                    "{token}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn encode_matches_rfc_4648_without_padding() {
+        assert_eq!(Encoding::Base32.encode(b"foobar"), "MZXW6YTBOI");
+        assert_eq!(Encoding::Base64Url.encode(b"fo"), "Zm8");
+        assert_eq!(Encoding::Base64Url.encode(&[0xfb, 0xff]), "-_8");
+    }
+}
@@ -0,0 +1,1092 @@
+use rand::rngs::{OsRng, StdRng};
+use rand::seq::SliceRandom;
+use rand::{Rng, RngCore, SeedableRng};
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fmt;
+
+mod clipboard;
+mod defaults;
+mod encoding;
+mod luhn;
+mod output;
+mod passphrase;
+mod pattern;
+mod pronounceable;
+mod report;
+mod runs;
+mod unicode;
+
+pub use crate::clipboard::{Clipboard, SystemClipboard};
+pub use crate::defaults::{Class, Defaults, DefaultsError, DEFAULTS_FILE_NAME};
+pub use crate::encoding::{generate_encoded, Encoding, BASE32_ALPHABET, BASE64URL_ALPHABET};
+pub use crate::luhn::{generate_luhn_number, is_luhn_valid};
+pub use crate::output::{ClipboardOutput, MemoryOutput, Output, WriterOutput};
+pub use crate::passphrase::{
+    choose_words, choose_words_from, generate_passphrase, join_words, load_wordlist, Separator,
+    WordlistError, WORDLIST,
+};
+pub use crate::pattern::{generate_from_pattern, pattern_entropy_bits};
+pub use crate::pronounceable::{
+    generate_pronounceable, generate_pronounceable_with_digits, generate_unique_pronounceable,
+    pronounceable_entropy_bits, pronounceable_with_digits_entropy_bits, CONSONANTS, VOWELS,
+};
+pub use crate::report::PasswordReport;
+pub use crate::runs::{has_keyboard_run, has_sequential_run};
+pub use crate::unicode::{generate_from_chars, EMOJI};
+
+pub const UPPERCASE: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+pub const LOWERCASE: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+pub const DIGITS: &[u8] = b"0123456789";
+pub const SYMBOLS: &[u8] = b"!@#$%^&*()-_=+[]{};:,.<>?/";
+
+/// Shortest password any mode will produce.
+pub const MIN_LENGTH: usize = 1;
+/// Longest password any mode will produce, so a typo like `1000000000`
+/// fails fast instead of trying to allocate it. The CLI holds word,
+/// syllable and digit counts to the same limit.
+pub const MAX_LENGTH: usize = 4096;
+
+/// How many duplicates in a row `generate_unique` tolerates before deciding
+/// there's nothing new left to draw.
+pub const UNIQUE_RETRIES: usize = 1000;
+
+/// Characters that are easily confused with one another when read aloud or
+/// in certain fonts: `0`/`O`/`o`, `1`/`l`/`I`/`|`, `5`/`S`, `2`/`Z`, `8`/`B`.
+pub const AMBIGUOUS: &[u8] = b"0Oo1lI|5S2Z8B";
+
+/// Which character classes a password draws from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CharClasses {
+    pub upper: bool,
+    pub lower: bool,
+    pub digits: bool,
+    pub symbols: bool,
+}
+
+impl CharClasses {
+    /// No classes enabled, a starting point for switching classes on one by one.
+    pub fn none() -> Self {
+        CharClasses {
+            upper: false,
+            lower: false,
+            digits: false,
+            symbols: false,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        !(self.upper || self.lower || self.digits || self.symbols)
+    }
+
+    /// The enabled classes, each with the `exclude`d bytes filtered out.
+    pub fn sets(&self, exclude: &[u8]) -> Vec<Vec<u8>> {
+        let mut sets: Vec<&[u8]> = Vec::new();
+        if self.upper {
+            sets.push(UPPERCASE);
+        }
+        if self.lower {
+            sets.push(LOWERCASE);
+        }
+        if self.digits {
+            sets.push(DIGITS);
+        }
+        if self.symbols {
+            sets.push(SYMBOLS);
+        }
+
+        sets.into_iter()
+            .map(|set| -> Vec<u8> {
+                set.iter()
+                    .copied()
+                    .filter(|byte| !exclude.contains(byte))
+                    .collect()
+            })
+            .filter(|set| !set.is_empty())
+            .collect()
+    }
+
+    pub fn pool(&self, exclude: &[u8]) -> Vec<u8> {
+        self.sets(exclude).concat()
+    }
+}
+
+/// Upper, lower and digits.
+impl Default for CharClasses {
+    fn default() -> Self {
+        CharClasses {
+            upper: true,
+            lower: true,
+            digits: true,
+            symbols: false,
+        }
+    }
+}
+
+/// Everything `build` needs to produce a password.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PasswordConfig {
+    pub length: usize,
+    pub classes: CharClasses,
+    /// Bytes that must never appear, e.g. `AMBIGUOUS`.
+    pub exclude: Vec<u8>,
+    /// Sample uniformly from exactly these characters instead of `classes`.
+    pub charset: Option<String>,
+    /// Seed for a deterministic `StdRng`. `None` uses `OsRng`.
+    pub seed: Option<u64>,
+    /// Never use the same character twice.
+    pub unique: bool,
+    /// Fixed text put before and after the random core, for policies like
+    /// "must start with `Aa1!`". `length` counts the core only. Anyone who
+    /// knows the policy knows these too, so they add no entropy.
+    pub prefix: String,
+    pub suffix: String,
+}
+
+impl PasswordConfig {
+    /// Every byte the password may be drawn from.
+    pub fn pool(&self) -> Vec<u8> {
+        match &self.charset {
+            Some(charset) => {
+                let mut pool: Vec<u8> = Vec::new();
+                for byte in charset.bytes() {
+                    if !pool.contains(&byte) && !self.exclude.contains(&byte) {
+                        pool.push(byte);
+                    }
+                }
+                pool
+            }
+            None => self.classes.pool(&self.exclude),
+        }
+    }
+
+    /// `core` with `prefix` and `suffix` around it.
+    pub fn wrap(&self, core: &str) -> String {
+        format!("{}{core}{}", self.prefix, self.suffix)
+    }
+}
+
+impl Default for PasswordConfig {
+    fn default() -> Self {
+        PasswordConfig {
+            length: 12,
+            classes: CharClasses::default(),
+            exclude: Vec::new(),
+            charset: None,
+            seed: None,
+            unique: false,
+            prefix: String::new(),
+            suffix: String::new(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum PasswordError {
+    /// No characters are left to sample from.
+    EmptyCharset,
+    /// The length can't fit one character from every enabled class.
+    LengthTooSmall { length: usize, classes: usize },
+    /// The requested length is outside `MIN_LENGTH..=MAX_LENGTH`.
+    InvalidLength(usize),
+    /// A custom charset contained something other than ASCII.
+    NonAsciiCharset,
+    /// `unique` was requested but the pool has fewer characters than the
+    /// requested length.
+    PoolTooSmall { length: usize, pool_size: usize },
+    /// A password pattern used a token other than `L`, `d` or `s`.
+    InvalidPatternToken(char),
+    /// The length and pool can't reach the entropy asked for. `min_length`
+    /// is the shortest length that would, when the pool allows one.
+    InsufficientEntropy {
+        needed: f64,
+        achievable: f64,
+        min_length: Option<usize>,
+    },
+    /// Like `InsufficientEntropy`, for a passphrase: `min_words` is the
+    /// smallest word count that would reach `needed`.
+    TooFewWords {
+        needed: f64,
+        achievable: f64,
+        min_words: Option<usize>,
+    },
+    /// Couldn't find `requested` distinct values before running out of
+    /// retries; `produced` were found.
+    NotEnoughUnique { requested: usize, produced: usize },
+    /// Every one of `attempts` tries had a keyboard or sequential run.
+    RunsUnavoidable { attempts: usize },
+}
+
+impl fmt::Display for PasswordError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PasswordError::EmptyCharset => {
+                write!(f, "the character set is empty, nothing to generate from")
+            }
+            PasswordError::LengthTooSmall { length, classes } => write!(
+                f,
+                "a length of {length} can't fit one character from each of the {classes} enabled classes"
+            ),
+            PasswordError::InvalidLength(length) => {
+                write!(
+                    f,
+                    "password length must be between {MIN_LENGTH} and {MAX_LENGTH}, got {length}"
+                )
+            }
+            PasswordError::NonAsciiCharset => {
+                write!(f, "custom charsets may only contain ASCII characters")
+            }
+            PasswordError::PoolTooSmall { length, pool_size } => write!(
+                f,
+                "can't pick {length} unique characters from a pool of {pool_size}"
+            ),
+            PasswordError::InvalidPatternToken(token) => write!(
+                f,
+                "unknown pattern token `{token}`, expected `L` (letter), `d` (digit) or `s` (symbol)"
+            ),
+            PasswordError::InsufficientEntropy {
+                needed,
+                achievable,
+                min_length,
+            } => {
+                write!(
+                    f,
+                    "only {achievable:.1} bits of entropy are possible, {needed:.1} required"
+                )?;
+                match min_length {
+                    Some(length) => write!(f, "; use a length of at least {length}"),
+                    None => Ok(()),
+                }
+            }
+            PasswordError::TooFewWords {
+                needed,
+                achievable,
+                min_words,
+            } => {
+                write!(
+                    f,
+                    "only {achievable:.1} bits of entropy are possible, {needed:.1} required"
+                )?;
+                match min_words {
+                    Some(words) => write!(f, "; use at least {words} words"),
+                    None => Ok(()),
+                }
+            }
+            PasswordError::NotEnoughUnique {
+                requested,
+                produced,
+            } => write!(
+                f,
+                "only found {produced} of {requested} unique values before giving up"
+            ),
+            PasswordError::RunsUnavoidable { attempts } => write!(
+                f,
+                "every one of {attempts} attempts had a keyboard or sequential run, try a longer length or a different mode"
+            ),
+        }
+    }
+}
+
+impl Error for PasswordError {}
+
+/// The RNG every mode draws from: a `StdRng` seeded with `seed`, so the same
+/// seed always gives the same output, or the operating system's CSPRNG
+/// when there's no seed.
+pub fn rng_from_seed(seed: Option<u64>) -> Box<dyn RngCore> {
+    match seed {
+        Some(seed) => Box::new(StdRng::seed_from_u64(seed)),
+        None => Box::new(OsRng),
+    }
+}
+
+/// Generates a password for `config` with `rng_from_seed(config.seed)`. The
+/// same seed and config always produce the same password.
+pub fn build(config: &PasswordConfig) -> Result<String, PasswordError> {
+    build_with_rng(&mut *rng_from_seed(config.seed), config)
+}
+
+/// Generates a password for `config` with the caller's RNG, ignoring
+/// `config.seed`.
+///
+/// A custom `charset` takes precedence over the character classes and is
+/// sampled uniformly, with no per-class guarantee. `prefix` and `suffix` are
+/// added around the `length` random characters.
+pub fn build_with_rng<R: Rng + ?Sized>(
+    rng: &mut R,
+    config: &PasswordConfig,
+) -> Result<String, PasswordError> {
+    build_from_pool(rng, config, &Pool::for_config(config)?)
+}
+
+/// Generates `count` passwords for `config`, working out the pool once and
+/// drawing every password from the same RNG (seeded from `config.seed`
+/// when set). With a seed, the first password is the one `build` gives.
+pub fn generate_batch(config: &PasswordConfig, count: usize) -> Result<Vec<String>, PasswordError> {
+    batch_with_rng(&mut *rng_from_seed(config.seed), config, count)
+}
+
+/// `count` distinct results of `generate`, in the order they were drawn. A
+/// duplicate is thrown away and drawn again, up to `UNIQUE_RETRIES` times in
+/// a row; after that `NotEnoughUnique` says how many were found.
+pub fn generate_unique<R: Rng + ?Sized>(
+    rng: &mut R,
+    count: usize,
+    mut generate: impl FnMut(&mut R) -> String,
+) -> Result<Vec<String>, PasswordError> {
+    let mut seen = HashSet::new();
+    let mut values = Vec::with_capacity(count);
+    let mut retries = 0;
+
+    while values.len() < count {
+        let value = generate(rng);
+        if seen.insert(value.clone()) {
+            values.push(value);
+            retries = 0;
+        } else if retries == UNIQUE_RETRIES {
+            return Err(PasswordError::NotEnoughUnique {
+                requested: count,
+                produced: values.len(),
+            });
+        } else {
+            retries += 1;
+        }
+    }
+    Ok(values)
+}
+
+fn batch_with_rng<R: Rng + ?Sized>(
+    rng: &mut R,
+    config: &PasswordConfig,
+    count: usize,
+) -> Result<Vec<String>, PasswordError> {
+    let pool = Pool::for_config(config)?;
+    (0..count)
+        .map(|_| build_from_pool(rng, config, &pool))
+        .collect()
+}
+
+/// How often each character turns up across `samples` passwords for
+/// `config`. With a custom `charset`, every pool character should land close
+/// to `samples * config.length / pool size` times; one that's missing or far
+/// off points at a sampling bug, like a range that stops one short of `Z`.
+/// The one-per-class guarantee skews class-based pools towards the smaller
+/// classes, so compare those class by class.
+pub fn char_distribution(
+    samples: usize,
+    config: &PasswordConfig,
+) -> Result<HashMap<char, usize>, PasswordError> {
+    let mut counts = HashMap::new();
+    for password in generate_batch(config, samples)? {
+        for c in password.chars() {
+            *counts.entry(c).or_insert(0) += 1;
+        }
+    }
+    Ok(counts)
+}
+
+/// The characters `config` draws from, worked out ahead of generating.
+enum Pool {
+    /// A custom charset, deduplicated and with exclusions removed.
+    Charset(Vec<u8>),
+    /// One set per enabled class.
+    Classes(Vec<Vec<u8>>),
+}
+
+impl Pool {
+    fn for_config(config: &PasswordConfig) -> Result<Pool, PasswordError> {
+        match &config.charset {
+            Some(charset) if !charset.is_ascii() => Err(PasswordError::NonAsciiCharset),
+            Some(_) => Ok(Pool::Charset(config.pool())),
+            None => Ok(Pool::Classes(config.classes.sets(&config.exclude))),
+        }
+    }
+}
+
+fn build_from_pool<R: Rng + ?Sized>(
+    rng: &mut R,
+    config: &PasswordConfig,
+    pool: &Pool,
+) -> Result<String, PasswordError> {
+    let core = match pool {
+        Pool::Charset(charset) if config.unique => {
+            generate_unique_password(rng, config.length, charset)
+        }
+        Pool::Charset(charset) => generate_password(rng, config.length, charset),
+        Pool::Classes(sets) => with_policy(rng, config.length, sets, config.unique),
+    }?;
+
+    Ok(config.wrap(&core))
+}
+
+/// `InvalidLength` unless `length` is within `MIN_LENGTH..=MAX_LENGTH`.
+pub fn check_length(length: usize) -> Result<(), PasswordError> {
+    if (MIN_LENGTH..=MAX_LENGTH).contains(&length) {
+        Ok(())
+    } else {
+        Err(PasswordError::InvalidLength(length))
+    }
+}
+
+/// Draws `password_length` characters uniformly from `charset`.
+///
+/// The caller supplies the RNG; `build` passes `OsRng` so passwords always
+/// come from the operating system's CSPRNG.
+pub fn generate_password<R: Rng + ?Sized>(
+    rng: &mut R,
+    password_length: usize,
+    charset: &[u8],
+) -> Result<String, PasswordError> {
+    if charset.is_empty() {
+        return Err(PasswordError::EmptyCharset);
+    }
+    check_length(password_length)?;
+
+    Ok(sample(rng, password_length, charset)
+        .into_iter()
+        .map(char::from)
+        .collect())
+}
+
+/// Like `generate_password`, but samples without replacement so no
+/// character appears twice.
+pub fn generate_unique_password<R: Rng + ?Sized>(
+    rng: &mut R,
+    password_length: usize,
+    charset: &[u8],
+) -> Result<String, PasswordError> {
+    if charset.is_empty() {
+        return Err(PasswordError::EmptyCharset);
+    }
+    check_length(password_length)?;
+    if password_length > charset.len() {
+        return Err(PasswordError::PoolTooSmall {
+            length: password_length,
+            pool_size: charset.len(),
+        });
+    }
+
+    let mut pool = charset.to_vec();
+    pool.shuffle(rng);
+    pool.truncate(password_length);
+
+    Ok(pool.into_iter().map(char::from).collect())
+}
+
+fn sample<R: Rng + ?Sized>(rng: &mut R, count: usize, pool: &[u8]) -> Vec<u8> {
+    (0..count)
+        .map(|_| pool[rng.gen_range(0..pool.len())])
+        .collect()
+}
+
+/// Like `generate_password`, but guarantees at least one character from
+/// every enabled class. The guaranteed characters are shuffled in with the
+/// rest so they don't always sit at the front. Bytes in `exclude` never
+/// appear.
+pub fn generate_password_with_policy<R: Rng + ?Sized>(
+    rng: &mut R,
+    password_length: usize,
+    classes: CharClasses,
+    exclude: &[u8],
+) -> Result<String, PasswordError> {
+    with_policy(rng, password_length, &classes.sets(exclude), false)
+}
+
+fn with_policy<R: Rng + ?Sized>(
+    rng: &mut R,
+    password_length: usize,
+    sets: &[Vec<u8>],
+    unique: bool,
+) -> Result<String, PasswordError> {
+    let pool = sets.concat();
+    if pool.is_empty() {
+        return Err(PasswordError::EmptyCharset);
+    }
+    check_length(password_length)?;
+    if password_length < sets.len() {
+        return Err(PasswordError::LengthTooSmall {
+            length: password_length,
+            classes: sets.len(),
+        });
+    }
+    if unique && password_length > pool.len() {
+        return Err(PasswordError::PoolTooSmall {
+            length: password_length,
+            pool_size: pool.len(),
+        });
+    }
+
+    let mut password: Vec<u8> = sets
+        .iter()
+        .map(|set| set[rng.gen_range(0..set.len())])
+        .collect();
+    let remaining = password_length - sets.len();
+    if unique {
+        let mut rest: Vec<u8> = pool
+            .into_iter()
+            .filter(|byte| !password.contains(byte))
+            .collect();
+        rest.shuffle(rng);
+        password.extend_from_slice(&rest[..remaining]);
+    } else {
+        password.extend(sample(rng, remaining, &pool));
+    }
+    password.shuffle(rng);
+
+    Ok(password.into_iter().map(char::from).collect())
+}
+
+/// Estimated entropy of a secret made of `length` independent picks from a
+/// pool of `pool_size` symbols: `length * log2(pool_size)`.
+///
+/// Works for characters and for passphrase words alike.
+pub fn password_entropy_bits(length: usize, pool_size: usize) -> f64 {
+    if pool_size == 0 {
+        return 0.0;
+    }
+    length as f64 * (pool_size as f64).log2()
+}
+
+/// How many characters of each class a password contains.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ClassCounts {
+    pub upper: usize,
+    pub lower: usize,
+    pub digits: usize,
+    /// ASCII punctuation, a superset of `SYMBOLS`.
+    pub symbols: usize,
+    /// Anything else: spaces, control characters, non-ASCII.
+    pub other: usize,
+}
+
+pub fn classify(password: &str) -> ClassCounts {
+    let mut counts = ClassCounts::default();
+    for c in password.chars() {
+        if c.is_ascii_uppercase() {
+            counts.upper += 1;
+        } else if c.is_ascii_lowercase() {
+            counts.lower += 1;
+        } else if c.is_ascii_digit() {
+            counts.digits += 1;
+        } else if c.is_ascii_punctuation() {
+            counts.symbols += 1;
+        } else {
+            counts.other += 1;
+        }
+    }
+    counts
+}
+
+/// `password` with everything but its first and last character replaced by
+/// `*`, e.g. `A**********9`, for showing on a shared screen. Passwords of one
+/// or two characters are masked completely, since showing both ends would
+/// show all of it.
+pub fn mask(password: &str) -> String {
+    let length = password.chars().count();
+    if length <= 2 {
+        return "*".repeat(length);
+    }
+
+    let mut chars = password.chars();
+    let first = chars.next().unwrap_or_default();
+    let last = chars.next_back().unwrap_or_default();
+    format!("{first}{}{last}", "*".repeat(length - 2))
+}
+
+/// Checks that `length` picks from a pool of `pool_size` reach `needed` bits,
+/// returning the achievable entropy if they do.
+pub fn check_min_entropy(
+    length: usize,
+    pool_size: usize,
+    needed: f64,
+) -> Result<f64, PasswordError> {
+    let achievable = password_entropy_bits(length, pool_size);
+    if achievable >= needed {
+        return Ok(achievable);
+    }
+
+    Err(PasswordError::InsufficientEntropy {
+        needed,
+        achievable,
+        min_length: min_length_for(needed, pool_size),
+    })
+}
+
+/// `check_min_entropy` for a passphrase of `word_count` words from a list of
+/// `wordlist_size`, failing with `TooFewWords` so the fix is given in words.
+pub fn check_min_passphrase_entropy(
+    word_count: usize,
+    wordlist_size: usize,
+    needed: f64,
+) -> Result<f64, PasswordError> {
+    check_min_entropy(word_count, wordlist_size, needed).map_err(|err| match err {
+        PasswordError::InsufficientEntropy {
+            needed,
+            achievable,
+            min_length,
+        } => PasswordError::TooFewWords {
+            needed,
+            achievable,
+            min_words: min_length,
+        },
+        err => err,
+    })
+}
+
+/// The shortest length whose entropy reaches `bits` with a pool of
+/// `pool_size`, or `None` if the pool is too small to add any entropy.
+pub fn min_length_for(bits: f64, pool_size: usize) -> Option<usize> {
+    if pool_size < 2 {
+        return None;
+    }
+    Some((bits / (pool_size as f64).log2()).ceil().max(0.0) as usize)
+}
+
+/// Coarse label for an entropy estimate: under 40 bits is `"weak"`, under 60
+/// `"fair"`, under 80 `"strong"`, anything above `"very strong"`.
+pub fn strength_label(entropy_bits: f64) -> &'static str {
+    if entropy_bits < 40.0 {
+        "weak"
+    } else if entropy_bits < 60.0 {
+        "fair"
+    } else if entropy_bits < 80.0 {
+        "strong"
+    } else {
+        "very strong"
+    }
+}
+
+/// Compares two byte strings, such as a stored and a typed password,
+/// without returning at the first difference, so the time taken says
+/// nothing about how many leading bytes matched. Only the lengths are
+/// compared up front.
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seeded(seed: u64) -> StdRng {
+        StdRng::seed_from_u64(seed)
+    }
+
+    #[test]
+    fn generate_password_reaches_both_ends_of_the_uppercase_range() {
+        let mut rng = seeded(1);
+        let letters: String = (0..1000)
+            .map(|_| generate_password(&mut rng, 12, UPPERCASE).unwrap())
+            .collect();
+
+        assert!(letters.contains('A'));
+        assert!(letters.contains('Z'));
+    }
+
+    fn all_classes() -> CharClasses {
+        CharClasses {
+            upper: true,
+            lower: true,
+            digits: true,
+            symbols: true,
+        }
+    }
+
+    fn has_every_class(password: &str) -> bool {
+        let counts = classify(password);
+        counts.upper > 0 && counts.lower > 0 && counts.digits > 0 && counts.symbols > 0
+    }
+
+    #[test]
+    fn policy_fits_every_class_when_length_equals_class_count() {
+        let mut rng = seeded(3);
+        for _ in 0..200 {
+            let password = generate_password_with_policy(&mut rng, 4, all_classes(), &[]).unwrap();
+            assert_eq!(password.len(), 4);
+            assert!(has_every_class(&password), "{password}");
+        }
+    }
+
+    #[test]
+    fn policy_covers_every_class_in_longer_passwords() {
+        let mut rng = seeded(3);
+        for _ in 0..200 {
+            let password = generate_password_with_policy(&mut rng, 16, all_classes(), &[]).unwrap();
+            assert_eq!(password.len(), 16);
+            assert!(has_every_class(&password), "{password}");
+        }
+    }
+
+    #[test]
+    fn policy_rejects_a_length_shorter_than_the_class_count() {
+        let result = generate_password_with_policy(&mut seeded(3), 3, all_classes(), &[]);
+        assert!(matches!(
+            result,
+            Err(PasswordError::LengthTooSmall {
+                length: 3,
+                classes: 4
+            })
+        ));
+    }
+
+    #[test]
+    fn generate_password_takes_any_rng() {
+        let first = generate_password(&mut seeded(4), 16, DIGITS).unwrap();
+        let again = generate_password(&mut seeded(4), 16, DIGITS).unwrap();
+        assert_eq!(first, again);
+
+        // A generator that always yields zero always picks the first character.
+        let mut zeros = rand::rngs::mock::StepRng::new(0, 0);
+        assert_eq!(generate_password(&mut zeros, 5, DIGITS).unwrap(), "00000");
+    }
+
+    #[test]
+    fn entropy_of_twelve_alphanumerics_is_about_71_5_bits() {
+        let bits = password_entropy_bits(12, 62);
+        assert!((bits - 71.45).abs() < 0.01, "{bits}");
+        assert_eq!(password_entropy_bits(8, 2), 8.0);
+        assert_eq!(password_entropy_bits(12, 0), 0.0);
+    }
+
+    #[test]
+    fn excluded_ambiguous_characters_never_appear() {
+        let config = PasswordConfig {
+            length: 32,
+            classes: all_classes(),
+            exclude: AMBIGUOUS.to_vec(),
+            seed: Some(8),
+            ..PasswordConfig::default()
+        };
+
+        for password in generate_batch(&config, 500).unwrap() {
+            assert!(
+                !password.bytes().any(|byte| AMBIGUOUS.contains(&byte)),
+                "{password}"
+            );
+        }
+    }
+
+    #[test]
+    fn each_error_comes_from_its_own_condition() {
+        let mut rng = seeded(10);
+
+        assert!(matches!(
+            generate_password(&mut rng, 8, b""),
+            Err(PasswordError::EmptyCharset)
+        ));
+        assert!(matches!(
+            generate_password_with_policy(&mut rng, 1, CharClasses::default(), &[]),
+            Err(PasswordError::LengthTooSmall { .. })
+        ));
+        assert!(matches!(
+            generate_password(&mut rng, 0, DIGITS),
+            Err(PasswordError::InvalidLength(0))
+        ));
+        let non_ascii = PasswordConfig {
+            charset: Some(String::from("abcé")),
+            ..PasswordConfig::default()
+        };
+        assert!(matches!(
+            build(&non_ascii),
+            Err(PasswordError::NonAsciiCharset)
+        ));
+        assert!(matches!(
+            generate_unique_password(&mut rng, 11, DIGITS),
+            Err(PasswordError::PoolTooSmall { .. })
+        ));
+    }
+
+    #[test]
+    fn errors_describe_the_problem() {
+        assert_eq!(
+            PasswordError::InvalidLength(0).to_string(),
+            "password length must be between 1 and 4096, got 0"
+        );
+        assert_eq!(
+            PasswordError::LengthTooSmall {
+                length: 2,
+                classes: 3
+            }
+            .to_string(),
+            "a length of 2 can't fit one character from each of the 3 enabled classes"
+        );
+    }
+
+    #[test]
+    fn custom_charset_is_the_only_source() {
+        let config = PasswordConfig {
+            length: 24,
+            charset: Some(String::from("abc")),
+            seed: Some(11),
+            ..PasswordConfig::default()
+        };
+
+        for password in generate_batch(&config, 100).unwrap() {
+            assert!(password.chars().all(|c| "abc".contains(c)), "{password}");
+        }
+    }
+
+    #[test]
+    fn empty_custom_charset_is_an_error() {
+        let config = PasswordConfig {
+            charset: Some(String::new()),
+            ..PasswordConfig::default()
+        };
+
+        assert!(matches!(build(&config), Err(PasswordError::EmptyCharset)));
+    }
+
+    #[test]
+    fn same_seed_same_password_and_different_seed_different_password() {
+        let config = |seed| PasswordConfig {
+            seed: Some(seed),
+            ..PasswordConfig::default()
+        };
+
+        assert_eq!(build(&config(12)).unwrap(), build(&config(12)).unwrap());
+        assert_ne!(build(&config(12)).unwrap(), build(&config(13)).unwrap());
+    }
+
+    #[test]
+    fn unique_password_never_repeats_a_character() {
+        let mut rng = seeded(13);
+        for _ in 0..100 {
+            let password = generate_unique_password(&mut rng, 10, DIGITS).unwrap();
+            let mut chars: Vec<char> = password.chars().collect();
+            chars.sort_unstable();
+            chars.dedup();
+            assert_eq!(chars.len(), 10, "{password}");
+        }
+    }
+
+    #[test]
+    fn unique_password_longer_than_the_pool_is_an_error() {
+        assert!(matches!(
+            generate_unique_password(&mut seeded(13), 27, LOWERCASE),
+            Err(PasswordError::PoolTooSmall {
+                length: 27,
+                pool_size: 26
+            })
+        ));
+    }
+
+    #[test]
+    fn strength_label_boundaries() {
+        assert_eq!(strength_label(39.9), "weak");
+        assert_eq!(strength_label(40.0), "fair");
+        assert_eq!(strength_label(59.9), "fair");
+        assert_eq!(strength_label(60.0), "strong");
+        assert_eq!(strength_label(79.9), "strong");
+        assert_eq!(strength_label(80.0), "very strong");
+    }
+
+    #[test]
+    fn check_min_entropy_passes_when_the_pool_is_big_enough() {
+        let bits = check_min_entropy(12, 62, 70.0).unwrap();
+        assert!((bits - 71.45).abs() < 0.01, "{bits}");
+    }
+
+    #[test]
+    fn check_min_entropy_fails_with_the_length_that_would_pass() {
+        match check_min_entropy(8, 62, 70.0) {
+            Err(PasswordError::InsufficientEntropy {
+                needed, min_length, ..
+            }) => {
+                assert_eq!(needed, 70.0);
+                assert_eq!(min_length, Some(12));
+            }
+            other => panic!("expected InsufficientEntropy, got {other:?}"),
+        }
+        assert!(matches!(
+            check_min_entropy(8, 1, 10.0),
+            Err(PasswordError::InsufficientEntropy {
+                min_length: None,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn passphrase_entropy_shortfall_is_given_in_words() {
+        // 7776 words carry about 12.9 bits each.
+        assert!(check_min_passphrase_entropy(6, 7776, 70.0).is_ok());
+
+        let err = check_min_passphrase_entropy(4, 7776, 70.0).unwrap_err();
+        assert!(matches!(
+            err,
+            PasswordError::TooFewWords {
+                min_words: Some(6),
+                ..
+            }
+        ));
+        assert!(err.to_string().ends_with("use at least 6 words"), "{err}");
+    }
+
+    #[test]
+    fn prefix_and_suffix_surround_a_core_of_the_configured_length() {
+        let config = PasswordConfig {
+            length: 10,
+            prefix: String::from("Aa1!"),
+            suffix: String::from("-x"),
+            seed: Some(68),
+            ..PasswordConfig::default()
+        };
+
+        let password = build(&config).unwrap();
+        let core = password
+            .strip_prefix("Aa1!")
+            .and_then(|rest| rest.strip_suffix("-x"))
+            .unwrap();
+
+        assert_eq!(core.len(), 10);
+        assert!(
+            core.bytes().all(|byte| byte.is_ascii_alphanumeric()),
+            "{core}"
+        );
+        assert_eq!(config.wrap("core"), "Aa1!core-x");
+    }
+
+    #[test]
+    fn constant_time_eq_compares_contents_and_length() {
+        assert!(constant_time_eq(b"hunter2", b"hunter2"));
+        assert!(constant_time_eq(b"", b""));
+        assert!(!constant_time_eq(b"hunter2", b"hunter3"));
+        assert!(!constant_time_eq(b"xunter2", b"hunter2"));
+        assert!(!constant_time_eq(b"hunter2", b"hunter"));
+    }
+
+    #[test]
+    fn check_length_accepts_exactly_min_to_max() {
+        assert!(matches!(
+            check_length(0),
+            Err(PasswordError::InvalidLength(0))
+        ));
+        assert!(check_length(1).is_ok());
+        assert!(check_length(4096).is_ok());
+        assert!(matches!(
+            check_length(4097),
+            Err(PasswordError::InvalidLength(4097))
+        ));
+    }
+
+    #[test]
+    fn generation_stops_at_the_length_limit() {
+        let mut rng = seeded(88);
+
+        assert_eq!(
+            generate_password(&mut rng, MAX_LENGTH, DIGITS)
+                .unwrap()
+                .len(),
+            MAX_LENGTH
+        );
+        assert!(generate_password(&mut rng, MAX_LENGTH + 1, DIGITS).is_err());
+        assert!(generate_password(&mut rng, MIN_LENGTH - 1, DIGITS).is_err());
+    }
+
+    #[test]
+    fn classify_counts_each_class() {
+        assert_eq!(
+            classify("Ab3$ é"),
+            ClassCounts {
+                upper: 1,
+                lower: 1,
+                digits: 1,
+                symbols: 1,
+                other: 2,
+            }
+        );
+        assert_eq!(
+            classify("CW4ngOdZ67hj"),
+            ClassCounts {
+                upper: 4,
+                lower: 5,
+                digits: 3,
+                symbols: 0,
+                other: 0,
+            }
+        );
+        assert_eq!(classify(""), ClassCounts::default());
+    }
+
+    #[test]
+    fn mask_keeps_only_the_ends_of_long_passwords() {
+        assert_eq!(mask("CW4ngOdZ67hj"), "C**********j");
+        assert_eq!(mask("abc"), "a*c");
+        assert_eq!(mask("🐶🐱🐭🐹"), "🐶**🐹");
+    }
+
+    #[test]
+    fn mask_hides_short_passwords_completely() {
+        assert_eq!(mask("ab"), "**");
+        assert_eq!(mask("a"), "*");
+        assert_eq!(mask(""), "");
+    }
+
+    #[test]
+    fn seeded_batch_starts_with_the_single_build() {
+        let config = PasswordConfig {
+            seed: Some(92),
+            ..PasswordConfig::default()
+        };
+        let batch = generate_batch(&config, 5).unwrap();
+
+        assert_eq!(batch.len(), 5);
+        assert_eq!(batch[0], build(&config).unwrap());
+        assert_eq!(batch, generate_batch(&config, 5).unwrap());
+        assert_ne!(batch[0], batch[1]);
+    }
+
+    #[test]
+    fn every_pool_character_turns_up_about_equally_often() {
+        let config = PasswordConfig {
+            length: 16,
+            charset: Some(String::from(
+                "0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ",
+            )),
+            seed: Some(93),
+            ..PasswordConfig::default()
+        };
+        let samples = 20_000;
+        let pool = config.pool();
+        let counts = char_distribution(samples, &config).unwrap();
+
+        let expected = (samples * config.length / pool.len()) as f64;
+        // Over four standard deviations, so only a real bias fails the test.
+        let tolerance = expected * 0.06;
+        for byte in pool {
+            let c = char::from(byte);
+            let count = counts.get(&c).copied().unwrap_or(0) as f64;
+            assert!(
+                (count - expected).abs() <= tolerance,
+                "{c} appeared {count} times, expected about {expected}"
+            );
+        }
+        assert_eq!(counts.len(), 62);
+    }
+
+    #[test]
+    fn one_seeded_rng_is_deterministic_across_modes() {
+        let draw = |seed| {
+            let mut rng = rng_from_seed(Some(seed));
+            (
+                build_with_rng(&mut *rng, &PasswordConfig::default()).unwrap(),
+                generate_passphrase(&mut *rng, 4, "-"),
+                generate_pronounceable(&mut *rng, 4),
+            )
+        };
+
+        assert_eq!(draw(99), draw(99));
+        assert_ne!(draw(99), draw(100));
+        assert_eq!(
+            draw(99).0,
+            build(&PasswordConfig {
+                seed: Some(99),
+                ..PasswordConfig::default()
+            })
+            .unwrap()
+        );
+    }
+}
@@ -0,0 +1,85 @@
+use crate::{check_length, PasswordError};
+use rand::Rng;
+
+/// A random number of `digit_count` digits whose last digit is the Luhn
+/// check digit of the rest, like a credit card number, for test data.
+pub fn generate_luhn_number<R: Rng + ?Sized>(
+    rng: &mut R,
+    digit_count: usize,
+) -> Result<String, PasswordError> {
+    check_length(digit_count)?;
+
+    let mut digits: Vec<u8> = (1..digit_count).map(|_| rng.gen_range(0..10)).collect();
+    digits.push(check_digit(&digits));
+
+    Ok(digits
+        .into_iter()
+        .map(|digit| char::from(b'0' + digit))
+        .collect())
+}
+
+/// Whether `number` is all digits and passes the Luhn check.
+pub fn is_luhn_valid(number: &str) -> bool {
+    if number.is_empty() || !number.bytes().all(|byte| byte.is_ascii_digit()) {
+        return false;
+    }
+
+    let digits: Vec<u8> = number.bytes().map(|byte| byte - b'0').collect();
+    luhn_sum(&digits, false).is_multiple_of(10)
+}
+
+/// The digit that makes `payload` followed by it pass the Luhn check.
+fn check_digit(payload: &[u8]) -> u8 {
+    ((10 - luhn_sum(payload, true) % 10) % 10) as u8
+}
+
+/// Sums the digits from the right, doubling every second one and taking
+/// 9 off any double above 9. `double_first` starts doubling at the rightmost
+/// digit, which is what a payload still missing its check digit needs.
+fn luhn_sum(digits: &[u8], double_first: bool) -> u32 {
+    digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &digit)| {
+            let digit = u32::from(digit);
+            if (i % 2 == 0) == double_first {
+                let doubled = digit * 2;
+                if doubled > 9 {
+                    doubled - 9
+                } else {
+                    doubled
+                }
+            } else {
+                digit
+            }
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn generated_numbers_pass_the_luhn_check() {
+        let mut rng = StdRng::seed_from_u64(71);
+        for digit_count in [1, 2, 8, 15, 16, 19, 64] {
+            let number = generate_luhn_number(&mut rng, digit_count).unwrap();
+
+            assert_eq!(number.len(), digit_count);
+            assert!(is_luhn_valid(&number), "{number}");
+        }
+    }
+
+    #[test]
+    fn luhn_check_against_known_numbers() {
+        assert!(is_luhn_valid("4539578763621486"));
+        assert!(is_luhn_valid("79927398713"));
+        assert!(!is_luhn_valid("79927398710"));
+        assert!(!is_luhn_valid("4539 5787"));
+        assert!(!is_luhn_valid(""));
+    }
+}
@@ -1,19 +1,895 @@
-use rand::Rng;
+use password_generator::{
+    build_with_rng, check_length, check_min_entropy, check_min_passphrase_entropy,
+    choose_words_from, classify, generate_encoded, generate_from_chars, generate_from_pattern,
+    generate_luhn_number, generate_pronounceable_with_digits, generate_unique, has_keyboard_run,
+    has_sequential_run, join_words, load_wordlist, mask, password_entropy_bits,
+    pattern_entropy_bits, pronounceable_with_digits_entropy_bits, rng_from_seed, strength_label,
+    CharClasses, Clipboard, ClipboardOutput, Defaults, Encoding, Output, PasswordConfig,
+    PasswordError, PasswordReport, Separator, SystemClipboard, WriterOutput, AMBIGUOUS, EMOJI,
+    MAX_LENGTH, MIN_LENGTH, WORDLIST,
+};
+use rand::RngCore;
+use std::fmt;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::path::Path;
+use std::process;
+
+/// Environment variable holding a default length.
+const LENGTH_ENV_VAR: &str = "PASSWORD_GEN_LENGTH";
+
+/// Produces one password per call in whichever mode was picked.
+type Generator = Box<dyn FnMut(&mut dyn RngCore) -> String>;
+
+/// Shortest run `--avoid-runs` rejects, long enough to let `df` or `12`
+/// through but not `asdf` or `1234`.
+const MIN_RUN: usize = 4;
+/// Passwords `--avoid-runs` draws before giving up.
+const RUN_RETRIES: usize = 100;
+
+/// Everything printed besides the passwords goes through here, so `--quiet`
+/// can drop it in one place. The passwords are written to `out` too, but
+/// never dropped.
+struct Log<W: Write> {
+    out: W,
+    quiet: bool,
+}
+
+impl<W: Write> Log<W> {
+    fn info(&mut self, message: impl fmt::Display) -> io::Result<()> {
+        if self.quiet {
+            return Ok(());
+        }
+        writeln!(self.out, "{message}")
+    }
+}
+
+/// How the passwords are shown, set by the output flags.
+#[derive(Debug, Default)]
+struct OutputOptions {
+    json: bool,
+    /// `--count` was given, so `--json` prints a list even for one password.
+    list: bool,
+    masked: bool,
+    copy: bool,
+    show_entropy: bool,
+    show_strength: bool,
+    verbose: bool,
+}
 
 fn main() {
-    let password_length = std::env::args()
-        .nth(1)
-        .unwrap_or_else(|| "12".to_string())
-        .parse::<usize>()
-        .unwrap_or(12);
+    let mut config = PasswordConfig::default();
+    let mut separator = String::from("-");
+
+    let defaults = match Defaults::path().map(|path| Defaults::load(&path)) {
+        Some(Ok(defaults)) => defaults,
+        Some(Err(err)) => {
+            eprintln!("error: {err}");
+            process::exit(2);
+        }
+        None => None,
+    };
+    if let Some(defaults) = defaults {
+        defaults.apply(&mut config);
+        if let Some(file_separator) = defaults.separator {
+            separator = file_separator;
+        }
+    }
+    let mut password_length = None;
+    let mut classes = CharClasses::none();
+    let mut word_count = None;
+    let mut pronounceable = false;
+    let mut syllable_digits = 0;
+    let mut syllable_count = None;
+    let mut luhn = false;
+    let mut emoji = false;
+    let mut encoding = None;
+    let mut pattern = None;
+    let mut capitalize = false;
+    let mut wordlist_path = None;
+    let mut min_entropy = None;
+    let mut count = None;
+    let mut quiet = false;
+    let mut options = OutputOptions::default();
+    let mut avoid_runs = false;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--words" => word_count = Some(parse_value(&mut args, "--words")),
+            "--separator" => {
+                let value = next_value(&mut args, "--separator");
+                separator = match Separator::parse(&value) {
+                    Some(choice) => choice.as_str().to_string(),
+                    None => {
+                        eprintln!(
+                            "error: `--separator` expects `-`, `_`, `space` or `none`, got `{value}`"
+                        );
+                        process::exit(2);
+                    }
+                };
+            }
+            "--capitalize" => capitalize = true,
+            "--wordlist" => wordlist_path = Some(next_value(&mut args, "--wordlist")),
+            "--min-entropy" => min_entropy = Some(parse_value(&mut args, "--min-entropy")),
+            "--count" => match parse_value(&mut args, "--count") {
+                0 => {
+                    eprintln!("error: `--count` must be at least 1");
+                    process::exit(2);
+                }
+                n => {
+                    count = Some(n);
+                    options.list = true;
+                }
+            },
+            "--json" => options.json = true,
+            "--verbose" => options.verbose = true,
+            "--quiet" => quiet = true,
+            "--avoid-runs" => avoid_runs = true,
+            "--mask" => options.masked = true,
+            "--prefix" => config.prefix = next_value(&mut args, "--prefix"),
+            "--suffix" => config.suffix = next_value(&mut args, "--suffix"),
+            "--seed" => config.seed = Some(parse_value(&mut args, "--seed")),
+            "--charset" => config.charset = Some(next_value(&mut args, "--charset")),
+            "--show-entropy" => options.show_entropy = true,
+            "--show-strength" => options.show_strength = true,
+            "--copy" => options.copy = true,
+            "--pronounceable" => pronounceable = true,
+            "--luhn" => luhn = true,
+            "--emoji" => emoji = true,
+            "--encoding" => {
+                let name = next_value(&mut args, "--encoding");
+                encoding = Some(Encoding::parse(&name).unwrap_or_else(|| {
+                    eprintln!("error: `--encoding` expects `base32` or `base64url`, got `{name}`");
+                    process::exit(2);
+                }));
+            }
+            "--syllables" => {
+                pronounceable = true;
+                match parse_value(&mut args, "--syllables") {
+                    0 => {
+                        eprintln!("error: `--syllables` must be at least 1");
+                        process::exit(2);
+                    }
+                    n => syllable_count = Some(n),
+                }
+            }
+            "--with-digits" => {
+                pronounceable = true;
+                syllable_digits = parse_value(&mut args, "--with-digits");
+                check_count("--with-digits", syllable_digits);
+            }
+            "--pattern" => pattern = Some(next_value(&mut args, "--pattern")),
+            "--unique" => config.unique = true,
+            "--no-ambiguous" => config.exclude = AMBIGUOUS.to_vec(),
+            "--upper" => classes.upper = true,
+            "--lower" => classes.lower = true,
+            "--digits" => classes.digits = true,
+            "--symbols" => classes.symbols = true,
+            _ if arg.starts_with("--") => {
+                eprintln!("error: unknown flag `{arg}`");
+                process::exit(2);
+            }
+            _ if password_length.is_none() => match arg.parse::<usize>() {
+                Ok(length) => password_length = Some(length),
+                Err(_) => {
+                    eprintln!("error: `{arg}` is not a valid password length");
+                    process::exit(2);
+                }
+            },
+            _ => {
+                eprintln!("error: unexpected argument `{arg}`");
+                process::exit(2);
+            }
+        }
+    }
+
+    // Scripts can pipe the length in instead. Only modes that use a length
+    // look, so `--words`, `--pattern` and `--syllables` never wait on stdin.
+    let uses_length = word_count.is_none() && pattern.is_none() && syllable_count.is_none();
+    let stdin = (uses_length && !io::stdin().is_terminal()).then(|| io::stdin().lock());
+    let env_length = std::env::var(LENGTH_ENV_VAR).ok();
+    config.length = resolve_length(password_length, stdin, env_length.as_deref(), config.length)
+        .unwrap_or_else(|err| {
+            eprintln!("error: {err}");
+            process::exit(2);
+        });
+    if !classes.is_empty() {
+        config.classes = classes;
+    }
 
-    let password = generate_password(password_length);
+    // One RNG for every password, so a seeded `--count` run still varies
+    // from one password to the next.
+    let mut rng = rng_from_seed(config.seed);
 
-    println!("{password}");
+    // `build_with_rng` adds the prefix and suffix itself; the other modes
+    // wrap their output with this copy. Either way only the random core
+    // counts towards `bits`.
+    let affixes = config.clone();
+    // What each pick is drawn from, for `--verbose`, where there's a single
+    // pool to speak of.
+    let mut pool_size = None;
+    // Pronounceable words come from a far smaller space than random
+    // characters, so a `--count` batch of them is kept free of repeats.
+    let mut distinct = false;
+    let (bits, generate): (f64, Generator) = if let Some(word_count) = word_count {
+        check_count("--words", word_count);
+        let words: Vec<String> = match &wordlist_path {
+            Some(path) => load_wordlist(Path::new(path)).unwrap_or_else(|err| {
+                eprintln!("error: `{path}`: {err}");
+                process::exit(2);
+            }),
+            None => WORDLIST.lines().map(String::from).collect(),
+        };
+        // Entropy follows whichever list the words actually come from.
+        let wordlist_size = words.len();
+        pool_size = Some(wordlist_size);
+        if let Some(needed) = min_entropy {
+            check_min_passphrase_entropy(word_count, wordlist_size, needed)
+                .unwrap_or_else(|err| fail(err));
+        }
+        (
+            password_entropy_bits(word_count, wordlist_size),
+            Box::new(move |rng| {
+                let words: Vec<&str> = words.iter().map(String::as_str).collect();
+                affixes.wrap(&join_words(
+                    &choose_words_from(rng, &words, word_count),
+                    &separator,
+                    capitalize,
+                ))
+            }),
+        )
+    } else if let Some(pattern) = pattern {
+        let bits = pattern_entropy_bits(&pattern).unwrap_or_else(|err| fail(err));
+        require_entropy(bits, min_entropy);
+        (
+            bits,
+            Box::new(move |rng| {
+                affixes.wrap(&generate_from_pattern(rng, &pattern).unwrap_or_else(|err| fail(err)))
+            }),
+        )
+    } else if let Some(encoding) = encoding {
+        // `length` is in output characters, each carrying a fixed number of
+        // random bits.
+        let bits = (config.length * encoding.bits_per_char()) as f64;
+        pool_size = Some(encoding.alphabet().len());
+        require_entropy(bits, min_entropy);
+        let length = config.length;
+        (
+            bits,
+            Box::new(move |rng| {
+                affixes
+                    .wrap(&generate_encoded(rng, encoding, length).unwrap_or_else(|err| fail(err)))
+            }),
+        )
+    } else if luhn {
+        // The check digit is fixed by the others, so it adds nothing.
+        let bits = password_entropy_bits(config.length.saturating_sub(1), 10);
+        pool_size = Some(10);
+        require_entropy(bits, min_entropy);
+        let length = config.length;
+        (
+            bits,
+            Box::new(move |rng| {
+                affixes.wrap(&generate_luhn_number(rng, length).unwrap_or_else(|err| fail(err)))
+            }),
+        )
+    } else if emoji {
+        let bits = password_entropy_bits(config.length, EMOJI.len());
+        pool_size = Some(EMOJI.len());
+        require_entropy(bits, min_entropy);
+        let length = config.length;
+        (
+            bits,
+            Box::new(move |rng| {
+                affixes
+                    .wrap(&generate_from_chars(rng, length, EMOJI).unwrap_or_else(|err| fail(err)))
+            }),
+        )
+    } else if pronounceable {
+        // `--syllables` sets the count directly. Otherwise the length is a
+        // budget for the shortest possible result, not an exact length:
+        // every syllable is two to four letters, so `--pronounceable 12`
+        // gives six syllables and 12 to 24 letters. The digits take up part
+        // of the budget too.
+        match syllable_count {
+            Some(syllables) => check_count("--syllables", syllables),
+            None => check_length(config.length).unwrap_or_else(|err| fail(err)),
+        }
+        let syllables = syllable_budget(syllable_count, config.length, syllable_digits)
+            .unwrap_or_else(|err| {
+                eprintln!("error: {err}");
+                process::exit(2);
+            });
+        let bits = pronounceable_with_digits_entropy_bits(syllables, syllable_digits);
+        require_entropy(bits, min_entropy);
+        distinct = true;
+        (
+            bits,
+            Box::new(move |rng| {
+                affixes.wrap(&generate_pronounceable_with_digits(
+                    rng,
+                    syllables,
+                    syllable_digits,
+                ))
+            }),
+        )
+    } else {
+        pool_size = Some(config.pool().len());
+        if let Some(needed) = min_entropy {
+            check_min_entropy(config.length, config.pool().len(), needed)
+                .unwrap_or_else(|err| fail(err));
+        }
+        (
+            password_entropy_bits(config.length, config.pool().len()),
+            Box::new(move |rng| build_with_rng(rng, &config).unwrap_or_else(|err| fail(err))),
+        )
+    };
+
+    // Rejecting some passwords shaves a little off `bits`, which isn't
+    // worth estimating.
+    let mut generate = if avoid_runs {
+        without_runs(generate)
+    } else {
+        generate
+    };
+
+    let passwords: Vec<String> = match count {
+        Some(count) if distinct => {
+            generate_unique(&mut *rng, count, |rng| generate(rng)).unwrap_or_else(|err| fail(err))
+        }
+        _ => (0..count.unwrap_or(1))
+            .map(|_| generate(&mut rng))
+            .collect(),
+    };
+
+    let mut log = Log {
+        out: io::stdout().lock(),
+        quiet,
+    };
+    present(
+        &mut log,
+        &mut SystemClipboard,
+        &passwords,
+        bits,
+        pool_size,
+        &options,
+    )
+    .unwrap_or_else(|err| write_failed(err));
 }
 
-fn generate_password(password_length: usize) -> String {
-    (0..password_length)
-        .map(|_| rand::thread_rng().gen_range(b'A'..b'Z') as char)
-        .collect()
+/// Wraps `generate` so it draws again whenever a password has a keyboard
+/// or sequential run of `MIN_RUN`, failing after `RUN_RETRIES` tries.
+fn without_runs(mut generate: Generator) -> Generator {
+    Box::new(move |rng| {
+        (0..RUN_RETRIES)
+            .map(|_| generate(rng))
+            .find(|password| {
+                !has_keyboard_run(password, MIN_RUN) && !has_sequential_run(password, MIN_RUN)
+            })
+            .unwrap_or_else(|| {
+                fail(PasswordError::RunsUnavoidable {
+                    attempts: RUN_RETRIES,
+                })
+            })
+    })
+}
+
+/// Shows the passwords as `options` asks: copied or printed, plain or as
+/// JSON, followed by whatever extras were asked for. `--json` and `--mask`
+/// only decide what's printed; `--copy` always copies the plain passwords.
+fn present<W: Write, C: Clipboard>(
+    log: &mut Log<W>,
+    clipboard: &mut C,
+    passwords: &[String],
+    bits: f64,
+    pool_size: Option<usize>,
+    options: &OutputOptions,
+) -> io::Result<()> {
+    // `--mask` only changes what's printed; the clipboard gets the real thing.
+    let shown: Vec<String> = if options.masked {
+        passwords.iter().map(|password| mask(password)).collect()
+    } else {
+        passwords.to_vec()
+    };
+
+    if options.json {
+        let reports: Vec<PasswordReport> = shown
+            .iter()
+            .map(|password| PasswordReport::new(password.clone(), bits))
+            .collect();
+        // A single object unless `--count` asked for a list.
+        let json = if options.list {
+            serde_json::to_string(&reports)
+        } else {
+            serde_json::to_string(&reports[0])
+        }
+        .expect("reports always serialize");
+
+        // The report is what's printed, copied or not, so scripts reading
+        // stdout always get JSON; the clipboard still gets the passwords.
+        if options.copy {
+            copy_passwords(clipboard, passwords);
+        }
+        return print(log, &[json]);
+    }
+
+    emit(log, clipboard, passwords, &shown, options.copy)?;
+    if options.show_entropy {
+        log.info(format_args!("entropy: {bits:.1} bits"))?;
+    }
+    if options.show_strength {
+        log.info(format_args!("strength: {}", strength_label(bits)))?;
+    }
+    if options.verbose {
+        explain(log, passwords, pool_size)?;
+    }
+    Ok(())
+}
+
+/// What `--verbose` adds: the class make-up of each password and the pool
+/// it came from.
+fn explain<W: Write>(
+    log: &mut Log<W>,
+    passwords: &[String],
+    pool_size: Option<usize>,
+) -> io::Result<()> {
+    for password in passwords {
+        let counts = classify(password);
+        log.info(format_args!(
+            "composition: {} upper, {} lower, {} digits, {} symbols, {} other",
+            counts.upper, counts.lower, counts.digits, counts.symbols, counts.other
+        ))?;
+    }
+    if let Some(pool_size) = pool_size {
+        log.info(format_args!("pool size: {pool_size}"))?;
+    }
+    Ok(())
+}
+
+/// Sends the passwords to the clipboard with `copy` and prints only a
+/// confirmation, or prints `shown` (the same passwords, possibly masked).
+/// If the clipboard fails, `shown` is printed instead.
+fn emit<W: Write, C: Clipboard>(
+    log: &mut Log<W>,
+    clipboard: &mut C,
+    passwords: &[String],
+    shown: &[String],
+    copy: bool,
+) -> io::Result<()> {
+    if copy && copy_passwords(clipboard, passwords) {
+        return log.info("password copied to clipboard");
+    }
+    print(log, shown)
+}
+
+/// Puts `passwords` on the clipboard, one per line. Warns and returns
+/// `false` if that fails.
+fn copy_passwords<C: Clipboard>(clipboard: &mut C, passwords: &[String]) -> bool {
+    let mut clipboard = ClipboardOutput::new(clipboard);
+    match passwords
+        .iter()
+        .try_for_each(|password| clipboard.write_password(password))
+    {
+        Ok(()) => true,
+        Err(err) => {
+            eprintln!("warning: couldn't copy to clipboard ({err}), printing instead");
+            false
+        }
+    }
+}
+
+fn print<W: Write>(log: &mut Log<W>, lines: &[String]) -> io::Result<()> {
+    let mut printed = WriterOutput::new(&mut log.out);
+    lines
+        .iter()
+        .try_for_each(|line| printed.write_password(line))
+}
+
+/// How many syllables a pronounceable password gets: `--syllables` if it
+/// was given, otherwise as many as fit in `length` next to the digits. Each
+/// digit closes a group of at least one syllable, so there can't be more
+/// digits than syllables.
+fn syllable_budget(
+    syllable_count: Option<usize>,
+    length: usize,
+    digits: usize,
+) -> Result<usize, String> {
+    let syllables = match syllable_count {
+        Some(syllables) => syllables,
+        None if digits >= length => {
+            return Err(format!(
+                "`--with-digits {digits}` leaves no room for syllables in a length of {length}"
+            ))
+        }
+        None => (length - digits).div_ceil(2),
+    };
+    if digits > syllables {
+        return Err(format!(
+            "`--with-digits {digits}` needs at least as many syllables, got {syllables}"
+        ));
+    }
+    Ok(syllables)
+}
+
+/// Exits if `bits` falls short of `--min-entropy`, for modes where a longer
+/// length isn't a simple fix.
+fn require_entropy(bits: f64, min_entropy: Option<f64>) {
+    if let Some(needed) = min_entropy.filter(|&needed| bits < needed) {
+        fail(PasswordError::InsufficientEntropy {
+            needed,
+            achievable: bits,
+            min_length: None,
+        });
+    }
+}
+
+/// Exits unless `count`, given to `flag`, is within the same bounds as a
+/// length, so a typo can't ask for billions of words, syllables or digits.
+fn check_count(flag: &str, count: usize) {
+    if let Err(err) = bound_count(flag, count) {
+        eprintln!("error: {err}");
+        process::exit(exit_code(&PasswordError::InvalidLength(count)));
+    }
+}
+
+fn bound_count(flag: &str, count: usize) -> Result<usize, String> {
+    check_length(count)
+        .map(|()| count)
+        .map_err(|_| format!("`{flag}` must be between {MIN_LENGTH} and {MAX_LENGTH}, got {count}"))
+}
+
+fn write_failed(err: io::Error) -> ! {
+    eprintln!("error: couldn't write the password: {err}");
+    process::exit(1);
+}
+
+fn fail(err: PasswordError) -> ! {
+    eprintln!("error: {err}");
+    process::exit(exit_code(&err));
+}
+
+/// Exit status for each generation failure; 2 is reserved for usage errors.
+fn exit_code(err: &PasswordError) -> i32 {
+    match err {
+        PasswordError::EmptyCharset => 3,
+        PasswordError::LengthTooSmall { .. } => 4,
+        PasswordError::InvalidLength(_) => 5,
+        PasswordError::NonAsciiCharset => 6,
+        PasswordError::PoolTooSmall { .. } => 7,
+        PasswordError::InvalidPatternToken(_) => 8,
+        PasswordError::InsufficientEntropy { .. } | PasswordError::TooFewWords { .. } => 9,
+        PasswordError::NotEnoughUnique { .. } => 10,
+        PasswordError::RunsUnavoidable { .. } => 11,
+    }
+}
+
+/// The length to generate. A length argument beats one piped on `stdin`,
+/// which beats `PASSWORD_GEN_LENGTH` (`env`), which beats `fallback`, the
+/// defaults file's length or the built-in one. `stdin` is only read when
+/// there's no argument.
+fn resolve_length(
+    arg: Option<usize>,
+    stdin: Option<impl BufRead>,
+    env: Option<&str>,
+    fallback: usize,
+) -> Result<usize, String> {
+    if let Some(length) = arg {
+        return Ok(length);
+    }
+    if let Some(length) = stdin.map(read_length).transpose()?.flatten() {
+        return Ok(length);
+    }
+    Ok(length_from_env(env).unwrap_or(fallback))
+}
+
+/// The length in `PASSWORD_GEN_LENGTH`, if it's set to a number. Anything
+/// else is ignored with a warning.
+fn length_from_env(value: Option<&str>) -> Option<usize> {
+    let value = value?;
+    match value.trim().parse() {
+        Ok(length) => Some(length),
+        Err(_) => {
+            eprintln!("warning: ignoring {LENGTH_ENV_VAR}=`{value}`, it's not a number");
+            None
+        }
+    }
+}
+
+/// A length read from the first non-blank line of `reader`, or `None` if
+/// there's nothing but whitespace.
+fn read_length(reader: impl BufRead) -> Result<Option<usize>, String> {
+    for line in reader.lines() {
+        let line = line.map_err(|err| format!("couldn't read the length from stdin: {err}"))?;
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        return line
+            .parse()
+            .map(Some)
+            .map_err(|_| format!("`{line}` from stdin is not a valid password length"));
+    }
+    Ok(None)
+}
+
+/// The value following `flag`, exiting with a usage error if it's missing.
+fn next_value(args: &mut impl Iterator<Item = String>, flag: &str) -> String {
+    args.next().unwrap_or_else(|| {
+        eprintln!("error: `{flag}` expects a value");
+        process::exit(2);
+    })
+}
+
+fn parse_value<T: std::str::FromStr>(args: &mut impl Iterator<Item = String>, flag: &str) -> T {
+    let value = next_value(args, flag);
+    value.parse().unwrap_or_else(|_| {
+        eprintln!("error: `{value}` is not a valid value for `{flag}`");
+        process::exit(2);
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shown(passwords: &[&str], quiet: bool, options: &OutputOptions) -> String {
+        let passwords: Vec<String> = passwords.iter().map(|p| p.to_string()).collect();
+        let mut log = Log {
+            out: Vec::new(),
+            quiet,
+        };
+        present(
+            &mut log,
+            &mut FailingClipboard,
+            &passwords,
+            71.5,
+            Some(62),
+            options,
+        )
+        .unwrap();
+        String::from_utf8(log.out).unwrap()
+    }
+
+    struct FailingClipboard;
+
+    impl Clipboard for FailingClipboard {
+        fn set_text(&mut self, _text: &str) -> io::Result<()> {
+            Err(io::Error::other("no clipboard here"))
+        }
+    }
+
+    #[derive(Default)]
+    struct FakeClipboard {
+        text: Option<String>,
+    }
+
+    impl Clipboard for FakeClipboard {
+        fn set_text(&mut self, text: &str) -> io::Result<()> {
+            self.text = Some(text.to_string());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn copy_puts_the_password_on_the_clipboard_and_prints_a_confirmation() {
+        let mut log = Log {
+            out: Vec::new(),
+            quiet: false,
+        };
+        let mut clipboard = FakeClipboard::default();
+        let options = OutputOptions {
+            copy: true,
+            ..OutputOptions::default()
+        };
+        let passwords = vec![String::from("CW4ngOdZ67hj")];
+
+        present(&mut log, &mut clipboard, &passwords, 71.5, None, &options).unwrap();
+
+        assert_eq!(clipboard.text.as_deref(), Some("CW4ngOdZ67hj"));
+        assert_eq!(
+            String::from_utf8(log.out).unwrap(),
+            "password copied to clipboard\n"
+        );
+    }
+
+    #[test]
+    fn a_failing_clipboard_falls_back_to_printing() {
+        let options = OutputOptions {
+            copy: true,
+            ..OutputOptions::default()
+        };
+
+        assert_eq!(shown(&["CW4ngOdZ67hj"], false, &options), "CW4ngOdZ67hj\n");
+    }
+
+    #[test]
+    fn quiet_prints_only_the_password() {
+        let options = OutputOptions {
+            show_entropy: true,
+            show_strength: true,
+            verbose: true,
+            ..OutputOptions::default()
+        };
+
+        assert_eq!(shown(&["CW4ngOdZ67hj"], true, &options), "CW4ngOdZ67hj\n");
+    }
+
+    #[test]
+    fn extras_follow_the_password_without_quiet() {
+        let options = OutputOptions {
+            show_entropy: true,
+            ..OutputOptions::default()
+        };
+
+        assert_eq!(
+            shown(&["CW4ngOdZ67hj"], false, &options),
+            "CW4ngOdZ67hj\nentropy: 71.5 bits\n"
+        );
+    }
+
+    #[test]
+    fn length_is_read_from_the_first_non_blank_stdin_line() {
+        assert_eq!(read_length(&b"\n  \n 16 \n4\n"[..]), Ok(Some(16)));
+        assert_eq!(read_length(&b"\n\n"[..]), Ok(None));
+        assert!(read_length(&b"sixteen\n"[..]).is_err());
+    }
+
+    #[test]
+    fn piped_length_is_used_without_an_argument() {
+        assert_eq!(resolve_length(None, Some(&b"16\n"[..]), None, 12), Ok(16));
+        // Nothing piped after all: fall through to the default.
+        assert_eq!(resolve_length(None, Some(&b""[..]), None, 12), Ok(12));
+    }
+
+    #[test]
+    fn length_argument_beats_a_piped_one() {
+        assert_eq!(resolve_length(Some(8), Some(&b"16\n"[..]), None, 12), Ok(8));
+        // Stdin isn't even read, so something unparsable there doesn't matter.
+        assert_eq!(
+            resolve_length(Some(8), Some(&b"oops\n"[..]), None, 12),
+            Ok(8)
+        );
+    }
+
+    #[test]
+    fn environment_length_is_used_without_an_argument() {
+        assert_eq!(length_from_env(Some(" 20 ")), Some(20));
+        assert_eq!(resolve_length(None, None::<&[u8]>, Some("20"), 12), Ok(20));
+    }
+
+    #[test]
+    fn length_argument_beats_the_environment() {
+        assert_eq!(
+            resolve_length(Some(8), None::<&[u8]>, Some("20"), 12),
+            Ok(8)
+        );
+    }
+
+    #[test]
+    fn invalid_environment_length_is_ignored() {
+        assert_eq!(length_from_env(Some("twenty")), None);
+        assert_eq!(
+            resolve_length(None, None::<&[u8]>, Some("twenty"), 12),
+            Ok(12)
+        );
+        assert_eq!(resolve_length(None, None::<&[u8]>, None, 12), Ok(12));
+    }
+
+    #[test]
+    fn length_argument_beats_the_defaults_file() {
+        let path = std::env::temp_dir().join(format!(
+            "password-generator-{}-length-defaults.toml",
+            std::process::id()
+        ));
+        std::fs::write(&path, "length = 20\n").unwrap();
+        let defaults = Defaults::load(&path).unwrap().unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let mut config = PasswordConfig::default();
+        defaults.apply(&mut config);
+
+        assert_eq!(
+            resolve_length(None, None::<&[u8]>, None, config.length),
+            Ok(20)
+        );
+        assert_eq!(
+            resolve_length(Some(8), None::<&[u8]>, None, config.length),
+            Ok(8)
+        );
+    }
+
+    #[test]
+    fn syllables_argument_is_used_as_given() {
+        assert_eq!(syllable_budget(Some(5), 12, 0), Ok(5));
+        assert_eq!(syllable_budget(Some(5), 12, 5), Ok(5));
+    }
+
+    #[test]
+    fn digits_cannot_outnumber_the_syllables_argument() {
+        assert!(syllable_budget(Some(2), 12, 3).is_err());
+        assert!(syllable_budget(Some(2), 12, 50).is_err());
+    }
+
+    #[test]
+    fn length_leaves_room_for_the_digits() {
+        // Twelve letters' worth, less two digits, is five syllables.
+        assert_eq!(syllable_budget(None, 12, 2), Ok(5));
+        assert!(syllable_budget(None, 12, 12).is_err());
+        // Eight digits leave two syllables, too few to put them between.
+        assert!(syllable_budget(None, 12, 8).is_err());
+    }
+
+    #[test]
+    fn counts_share_the_length_limit() {
+        for flag in ["--words", "--syllables", "--with-digits"] {
+            assert_eq!(bound_count(flag, MIN_LENGTH), Ok(MIN_LENGTH));
+            assert_eq!(bound_count(flag, MAX_LENGTH), Ok(MAX_LENGTH));
+            assert!(bound_count(flag, 0).is_err());
+        }
+        assert_eq!(
+            bound_count("--with-digits", 100_000_000),
+            Err(String::from(
+                "`--with-digits` must be between 1 and 4096, got 100000000"
+            ))
+        );
+    }
+
+    #[test]
+    fn json_copy_puts_the_password_not_the_report_on_the_clipboard() {
+        let mut log = Log {
+            out: Vec::new(),
+            quiet: false,
+        };
+        let mut clipboard = FakeClipboard::default();
+        let options = OutputOptions {
+            json: true,
+            copy: true,
+            ..OutputOptions::default()
+        };
+        let passwords = vec![String::from("CW4ngOdZ67hj")];
+
+        present(&mut log, &mut clipboard, &passwords, 71.5, None, &options).unwrap();
+
+        assert_eq!(clipboard.text.as_deref(), Some("CW4ngOdZ67hj"));
+        let report: serde_json::Value = serde_json::from_slice(&log.out).unwrap();
+        assert_eq!(report["password"], "CW4ngOdZ67hj");
+    }
+
+    #[test]
+    fn masked_json_is_printed_while_the_clipboard_gets_the_password() {
+        let mut log = Log {
+            out: Vec::new(),
+            quiet: false,
+        };
+        let mut clipboard = FakeClipboard::default();
+        let options = OutputOptions {
+            json: true,
+            masked: true,
+            copy: true,
+            list: true,
+            ..OutputOptions::default()
+        };
+        let passwords = vec![String::from("CW4ngOdZ67hj"), String::from("calm-hard")];
+
+        present(&mut log, &mut clipboard, &passwords, 71.5, None, &options).unwrap();
+
+        assert_eq!(clipboard.text.as_deref(), Some("CW4ngOdZ67hj\ncalm-hard"));
+        let reports: serde_json::Value = serde_json::from_slice(&log.out).unwrap();
+        assert_eq!(reports[0]["password"], "C**********j");
+        assert_eq!(reports[1]["password"], "c*******d");
+    }
+
+    #[test]
+    fn masked_json_is_printed_when_the_clipboard_fails() {
+        let options = OutputOptions {
+            json: true,
+            masked: true,
+            copy: true,
+            ..OutputOptions::default()
+        };
+        let report: serde_json::Value =
+            serde_json::from_str(&shown(&["CW4ngOdZ67hj"], false, &options)).unwrap();
+
+        assert_eq!(report["password"], "C**********j");
+    }
 }
@@ -0,0 +1,103 @@
+use crate::Clipboard;
+use std::io::{self, Write};
+
+/// Where generated passwords go, one call per password.
+pub trait Output {
+    fn write_password(&mut self, password: &str) -> io::Result<()>;
+}
+
+/// Writes each password on its own line: to stdout, or to any other
+/// writer, such as a buffer to check in a test.
+pub struct WriterOutput<W: Write> {
+    out: W,
+}
+
+impl WriterOutput<io::Stdout> {
+    pub fn stdout() -> Self {
+        WriterOutput::new(io::stdout())
+    }
+}
+
+impl<W: Write> WriterOutput<W> {
+    pub fn new(out: W) -> Self {
+        WriterOutput { out }
+    }
+}
+
+impl<W: Write> Output for WriterOutput<W> {
+    fn write_password(&mut self, password: &str) -> io::Result<()> {
+        writeln!(self.out, "{password}")
+    }
+}
+
+/// Copies the passwords to a clipboard. Each write replaces the clipboard
+/// with every password so far, one per line, so none are lost.
+pub struct ClipboardOutput<C: Clipboard> {
+    clipboard: C,
+    copied: String,
+}
+
+impl<C: Clipboard> ClipboardOutput<C> {
+    pub fn new(clipboard: C) -> Self {
+        ClipboardOutput {
+            clipboard,
+            copied: String::new(),
+        }
+    }
+}
+
+impl<C: Clipboard> Output for ClipboardOutput<C> {
+    fn write_password(&mut self, password: &str) -> io::Result<()> {
+        if !self.copied.is_empty() {
+            self.copied.push('\n');
+        }
+        self.copied.push_str(password);
+        self.clipboard.set_text(&self.copied)
+    }
+}
+
+/// Keeps the passwords in memory, for tests and for callers that want to
+/// handle them some other way.
+#[derive(Debug, Default)]
+pub struct MemoryOutput {
+    pub passwords: Vec<String>,
+}
+
+impl Output for MemoryOutput {
+    fn write_password(&mut self, password: &str) -> io::Result<()> {
+        self.passwords.push(password.to_string());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{generate_batch, PasswordConfig};
+
+    #[test]
+    fn memory_output_receives_exactly_the_generated_passwords() {
+        let config = PasswordConfig {
+            seed: Some(7),
+            ..PasswordConfig::default()
+        };
+        let passwords = generate_batch(&config, 5).unwrap();
+
+        let mut output = MemoryOutput::default();
+        for password in &passwords {
+            output.write_password(password).unwrap();
+        }
+
+        assert_eq!(output.passwords, passwords);
+    }
+
+    #[test]
+    fn writer_output_puts_each_password_on_its_own_line() {
+        let mut buffer = Vec::new();
+        let mut output = WriterOutput::new(&mut buffer);
+        output.write_password("first").unwrap();
+        output.write_password("second").unwrap();
+
+        assert_eq!(String::from_utf8(buffer).unwrap(), "first\nsecond\n");
+    }
+}
@@ -0,0 +1,272 @@
+use rand::Rng;
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::path::Path;
+
+/// Default passphrase wordlist, one word per line, compiled into the binary.
+pub const WORDLIST: &str = include_str!("wordlist.txt");
+
+/// The separators `--separator` accepts between passphrase words.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Separator {
+    Hyphen,
+    Underscore,
+    Space,
+    /// Words run together, e.g. `CorrectHorse` with capitalization.
+    None,
+}
+
+impl Separator {
+    /// Accepts the separator itself (`-`, `_`, a single space, or an empty
+    /// string) or its name: `hyphen`, `underscore`, `space`, `none`.
+    pub fn parse(value: &str) -> Option<Separator> {
+        match value {
+            "-" | "hyphen" => Some(Separator::Hyphen),
+            "_" | "underscore" => Some(Separator::Underscore),
+            " " | "space" => Some(Separator::Space),
+            "" | "none" => Some(Separator::None),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Separator::Hyphen => "-",
+            Separator::Underscore => "_",
+            Separator::Space => " ",
+            Separator::None => "",
+        }
+    }
+}
+
+/// Builds a passphrase such as `correct-horse-battery-staple` by picking
+/// `word_count` words from the embedded wordlist and joining them with
+/// `separator`.
+pub fn generate_passphrase<R: Rng + ?Sized>(
+    rng: &mut R,
+    word_count: usize,
+    separator: &str,
+) -> String {
+    join_words(&choose_words(rng, word_count), separator, false)
+}
+
+/// `word_count` words picked uniformly from the embedded wordlist.
+pub fn choose_words<R: Rng + ?Sized>(rng: &mut R, word_count: usize) -> Vec<&'static str> {
+    let words: Vec<&str> = WORDLIST.lines().collect();
+    choose_words_from(rng, &words, word_count)
+}
+
+/// `word_count` words picked uniformly from `words`, which must not be
+/// empty.
+pub fn choose_words_from<'a, R: Rng + ?Sized>(
+    rng: &mut R,
+    words: &[&'a str],
+    word_count: usize,
+) -> Vec<&'a str> {
+    (0..word_count)
+        .map(|_| words[rng.gen_range(0..words.len())])
+        .collect()
+}
+
+#[derive(Debug)]
+pub enum WordlistError {
+    Io(io::Error),
+    /// The file had no words once blank lines were dropped.
+    Empty,
+}
+
+impl fmt::Display for WordlistError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WordlistError::Io(err) => write!(f, "couldn't read wordlist: {err}"),
+            WordlistError::Empty => write!(f, "wordlist has no words"),
+        }
+    }
+}
+
+impl Error for WordlistError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            WordlistError::Io(err) => Some(err),
+            WordlistError::Empty => None,
+        }
+    }
+}
+
+/// Reads a wordlist for `--wordlist`, one word per line. Lines are trimmed
+/// and blank ones skipped. Repeated words are kept only the first time, so
+/// the entropy worked out from the list's length isn't overstated.
+pub fn load_wordlist(path: &Path) -> Result<Vec<String>, WordlistError> {
+    let contents = std::fs::read_to_string(path).map_err(WordlistError::Io)?;
+    let mut seen = HashSet::new();
+    let words: Vec<String> = contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && seen.insert(*line))
+        .map(String::from)
+        .collect();
+
+    if words.is_empty() {
+        return Err(WordlistError::Empty);
+    }
+    Ok(words)
+}
+
+/// Joins already chosen `words` with `separator`, upper-casing the first
+/// letter of each one when `capitalize` is set.
+pub fn join_words(words: &[&str], separator: &str, capitalize: bool) -> String {
+    words
+        .iter()
+        .map(|word| {
+            if capitalize {
+                capitalize_word(word)
+            } else {
+                word.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+fn capitalize_word(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn seeded(seed: u64) -> StdRng {
+        StdRng::seed_from_u64(seed)
+    }
+
+    #[test]
+    fn passphrase_has_the_requested_number_of_listed_words() {
+        let passphrase = generate_passphrase(&mut seeded(5), 6, "-");
+        let words: Vec<&str> = passphrase.split('-').collect();
+
+        assert_eq!(words.len(), 6);
+        assert!(words
+            .iter()
+            .all(|word| WORDLIST.lines().any(|line| line == *word)));
+    }
+
+    #[test]
+    fn passphrase_uses_the_given_separator() {
+        let passphrase = generate_passphrase(&mut seeded(5), 4, " + ");
+
+        assert_eq!(passphrase.matches(" + ").count(), 3);
+        assert_eq!(
+            generate_passphrase(&mut seeded(5), 4, "-"),
+            passphrase.replace(" + ", "-")
+        );
+    }
+
+    #[test]
+    fn separator_parses_its_literal_and_its_name() {
+        for (literal, name, separator) in [
+            ("-", "hyphen", Separator::Hyphen),
+            ("_", "underscore", Separator::Underscore),
+            (" ", "space", Separator::Space),
+            ("", "none", Separator::None),
+        ] {
+            assert_eq!(Separator::parse(literal), Some(separator));
+            assert_eq!(Separator::parse(name), Some(separator));
+            assert_eq!(separator.as_str(), literal);
+        }
+        assert_eq!(Separator::parse("+"), None);
+    }
+
+    #[test]
+    fn join_words_with_each_separator() {
+        let words = ["correct", "horse"];
+
+        assert_eq!(
+            join_words(&words, Separator::Hyphen.as_str(), false),
+            "correct-horse"
+        );
+        assert_eq!(
+            join_words(&words, Separator::Underscore.as_str(), false),
+            "correct_horse"
+        );
+        assert_eq!(
+            join_words(&words, Separator::Space.as_str(), false),
+            "correct horse"
+        );
+        assert_eq!(
+            join_words(&words, Separator::None.as_str(), false),
+            "correcthorse"
+        );
+    }
+
+    #[test]
+    fn join_words_capitalizes_each_word() {
+        let words = ["correct", "horse"];
+
+        assert_eq!(
+            join_words(&words, Separator::None.as_str(), true),
+            "CorrectHorse"
+        );
+        assert_eq!(join_words(&words, "-", true), "Correct-Horse");
+        assert_eq!(join_words(&["éclair"], "", true), "Éclair");
+    }
+
+    fn temp_wordlist(name: &str, contents: &str) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "password-generator-{}-{name}.txt",
+            std::process::id()
+        ));
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn wordlist_lines_are_trimmed_and_blank_ones_skipped() {
+        let path = temp_wordlist("words", " alpha \n\nbeta\r\n  \ngamma\n");
+        let words = load_wordlist(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(words, ["alpha", "beta", "gamma"]);
+    }
+
+    #[test]
+    fn repeated_wordlist_lines_count_once() {
+        let path = temp_wordlist("repeats", "alpha\nbeta\n alpha\ngamma\nbeta\n");
+        let words = load_wordlist(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(words, ["alpha", "beta", "gamma"]);
+    }
+
+    #[test]
+    fn missing_wordlist_is_an_io_error() {
+        let path = std::env::temp_dir().join("password-generator-no-such-wordlist.txt");
+        assert!(matches!(load_wordlist(&path), Err(WordlistError::Io(_))));
+    }
+
+    #[test]
+    fn wordlist_of_only_blank_lines_is_empty() {
+        let path = temp_wordlist("blank", "\n   \n\n");
+        let result = load_wordlist(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(matches!(result, Err(WordlistError::Empty)));
+    }
+
+    #[test]
+    fn words_come_only_from_the_given_list() {
+        let words = ["alpha", "beta"];
+        let chosen = choose_words_from(&mut seeded(97), &words, 20);
+
+        assert_eq!(chosen.len(), 20);
+        assert!(chosen.iter().all(|word| words.contains(word)));
+    }
+}
@@ -0,0 +1,81 @@
+use crate::{
+    check_length, password_entropy_bits, PasswordError, DIGITS, LOWERCASE, SYMBOLS, UPPERCASE,
+};
+use rand::Rng;
+
+/// Characters a single pattern token may expand to: `L` is any letter, `d`
+/// a digit and `s` a symbol.
+fn token_pool(token: char) -> Option<Vec<u8>> {
+    match token {
+        'L' => Some([UPPERCASE, LOWERCASE].concat()),
+        'd' => Some(DIGITS.to_vec()),
+        's' => Some(SYMBOLS.to_vec()),
+        _ => None,
+    }
+}
+
+/// Expands a template such as `LLLdddss` one token at a time, returning an
+/// error on the first token that isn't `L`, `d` or `s`.
+pub fn generate_from_pattern<R: Rng + ?Sized>(
+    rng: &mut R,
+    pattern: &str,
+) -> Result<String, PasswordError> {
+    check_length(pattern.chars().count())?;
+
+    pattern
+        .chars()
+        .map(|token| {
+            let pool = token_pool(token).ok_or(PasswordError::InvalidPatternToken(token))?;
+            Ok(pool[rng.gen_range(0..pool.len())] as char)
+        })
+        .collect()
+}
+
+/// Entropy of a `generate_from_pattern` result, summed token by token.
+pub fn pattern_entropy_bits(pattern: &str) -> Result<f64, PasswordError> {
+    pattern
+        .chars()
+        .map(|token| {
+            let pool = token_pool(token).ok_or(PasswordError::InvalidPatternToken(token))?;
+            Ok(password_entropy_bits(1, pool.len()))
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn pattern_tokens_expand_to_their_classes() {
+        let mut rng = StdRng::seed_from_u64(18);
+        for _ in 0..100 {
+            let password = generate_from_pattern(&mut rng, "LLLdddss").unwrap();
+            let bytes = password.as_bytes();
+
+            assert_eq!(bytes.len(), 8);
+            assert!(bytes[..3].iter().all(u8::is_ascii_alphabetic), "{password}");
+            assert!(bytes[3..6].iter().all(u8::is_ascii_digit), "{password}");
+            assert!(
+                bytes[6..].iter().all(|byte| SYMBOLS.contains(byte)),
+                "{password}"
+            );
+        }
+    }
+
+    #[test]
+    fn unknown_pattern_token_is_an_error() {
+        let mut rng = StdRng::seed_from_u64(18);
+
+        assert!(matches!(
+            generate_from_pattern(&mut rng, "LLxd"),
+            Err(PasswordError::InvalidPatternToken('x'))
+        ));
+        assert!(matches!(
+            pattern_entropy_bits("LLxd"),
+            Err(PasswordError::InvalidPatternToken('x'))
+        ));
+    }
+}
@@ -0,0 +1,169 @@
+use crate::{generate_unique, password_entropy_bits, PasswordError, DIGITS};
+use rand::Rng;
+
+/// Consonant groups that start each syllable.
+pub const CONSONANTS: &[&str] = &[
+    "b", "c", "d", "f", "g", "h", "j", "k", "l", "m", "n", "p", "r", "s", "t", "v", "w", "z", "br",
+    "ch", "dr", "fl", "gr", "kr", "pl", "sh", "st", "th", "tr",
+];
+
+/// Vowel groups that end each syllable.
+pub const VOWELS: &[&str] = &["a", "e", "i", "o", "u", "ai", "ea", "oo", "ou"];
+
+/// Builds a pseudo-word such as `bacotipu` from `syllable_count` syllables,
+/// each a consonant group followed by a vowel group.
+///
+/// Groups are one or two letters long, so the result is between
+/// `2 * syllable_count` and `4 * syllable_count` letters.
+pub fn generate_pronounceable<R: Rng + ?Sized>(rng: &mut R, syllable_count: usize) -> String {
+    let mut word = String::new();
+    for _ in 0..syllable_count {
+        push_syllable(rng, &mut word);
+    }
+    word
+}
+
+/// Like `generate_pronounceable`, but splits the syllables into
+/// `digit_count` groups of near-equal size and ends each group with a random
+/// digit, e.g. `bako3tipu9` for four syllables and two digits.
+///
+/// The digits always sit at the same positions for a given pair of counts,
+/// so only the RNG decides the output.
+pub fn generate_pronounceable_with_digits<R: Rng + ?Sized>(
+    rng: &mut R,
+    syllable_count: usize,
+    digit_count: usize,
+) -> String {
+    if digit_count == 0 {
+        return generate_pronounceable(rng, syllable_count);
+    }
+
+    let mut word = String::new();
+    for group in 0..digit_count {
+        let start = syllable_count * group / digit_count;
+        let end = syllable_count * (group + 1) / digit_count;
+        for _ in start..end {
+            push_syllable(rng, &mut word);
+        }
+        word.push(char::from(DIGITS[rng.gen_range(0..DIGITS.len())]));
+    }
+    word
+}
+
+/// `count` distinct results of `generate_pronounceable`, in the order they
+/// were drawn, or `NotEnoughUnique` once `generate_unique` gives up, since a
+/// small `syllable_count` only has so many words.
+pub fn generate_unique_pronounceable<R: Rng + ?Sized>(
+    rng: &mut R,
+    count: usize,
+    syllable_count: usize,
+) -> Result<Vec<String>, PasswordError> {
+    generate_unique(rng, count, |rng| {
+        generate_pronounceable(rng, syllable_count)
+    })
+}
+
+fn push_syllable<R: Rng + ?Sized>(rng: &mut R, word: &mut String) {
+    word.push_str(CONSONANTS[rng.gen_range(0..CONSONANTS.len())]);
+    word.push_str(VOWELS[rng.gen_range(0..VOWELS.len())]);
+}
+
+/// Entropy of a `generate_pronounceable` result: every syllable is one pick
+/// from `CONSONANTS.len() * VOWELS.len()` combinations.
+pub fn pronounceable_entropy_bits(syllable_count: usize) -> f64 {
+    password_entropy_bits(syllable_count, CONSONANTS.len() * VOWELS.len())
+}
+
+/// Entropy of a `generate_pronounceable_with_digits` result: the syllables
+/// plus one pick from ten for every digit.
+pub fn pronounceable_with_digits_entropy_bits(syllable_count: usize, digit_count: usize) -> f64 {
+    pronounceable_entropy_bits(syllable_count) + password_entropy_bits(digit_count, DIGITS.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn seeded(seed: u64) -> StdRng {
+        StdRng::seed_from_u64(seed)
+    }
+
+    /// Whether `word` splits into consonant-group, vowel-group syllables.
+    fn is_syllables(word: &str) -> bool {
+        if word.is_empty() {
+            return true;
+        }
+        CONSONANTS.iter().any(|consonant| {
+            word.strip_prefix(consonant).is_some_and(|rest| {
+                VOWELS
+                    .iter()
+                    .any(|vowel| rest.strip_prefix(vowel).is_some_and(is_syllables))
+            })
+        })
+    }
+
+    #[test]
+    fn pronounceable_words_are_built_from_the_tables() {
+        let mut rng = seeded(17);
+        for syllables in 1..=8 {
+            let word = generate_pronounceable(&mut rng, syllables);
+            assert!(
+                (2 * syllables..=4 * syllables).contains(&word.len()),
+                "{word}"
+            );
+            assert!(is_syllables(&word), "{word}");
+        }
+    }
+
+    #[test]
+    fn with_digits_has_exactly_that_many_digits_among_letters() {
+        let mut rng = seeded(69);
+        for (syllables, digits) in [(4, 2), (5, 3), (3, 3), (6, 1)] {
+            let word = generate_pronounceable_with_digits(&mut rng, syllables, digits);
+
+            assert_eq!(
+                word.chars().filter(char::is_ascii_digit).count(),
+                digits,
+                "{word}"
+            );
+            assert!(
+                word.chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit()),
+                "{word}"
+            );
+            assert!(word.ends_with(|c: char| c.is_ascii_digit()), "{word}");
+            let letters: String = word.chars().filter(char::is_ascii_lowercase).collect();
+            assert!(is_syllables(&letters), "{word}");
+        }
+    }
+
+    #[test]
+    fn unique_pronounceable_gives_the_requested_count_without_repeats() {
+        let words = generate_unique_pronounceable(&mut seeded(98), 200, 2).unwrap();
+        let mut sorted = words.clone();
+        sorted.sort_unstable();
+        sorted.dedup();
+
+        assert_eq!(words.len(), 200);
+        assert_eq!(sorted.len(), 200);
+    }
+
+    #[test]
+    fn unique_pronounceable_gives_up_when_the_syllable_space_runs_out() {
+        // One syllable has at most CONSONANTS.len() * VOWELS.len() spellings.
+        let requested = CONSONANTS.len() * VOWELS.len() + 1;
+
+        match generate_unique_pronounceable(&mut seeded(98), requested, 1) {
+            Err(PasswordError::NotEnoughUnique {
+                requested: asked,
+                produced,
+            }) => {
+                assert_eq!(asked, requested);
+                assert!(produced < requested);
+            }
+            other => panic!("expected NotEnoughUnique, got {other:?}"),
+        }
+    }
+}
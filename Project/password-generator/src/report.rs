@@ -0,0 +1,46 @@
+use crate::strength_label;
+use serde::{Deserialize, Serialize};
+
+/// What `--json` prints for each generated password.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PasswordReport {
+    pub password: String,
+    /// Length in characters, not bytes.
+    pub length: usize,
+    pub entropy_bits: f64,
+    pub strength: String,
+}
+
+impl PasswordReport {
+    pub fn new(password: String, entropy_bits: f64) -> Self {
+        PasswordReport {
+            length: password.chars().count(),
+            password,
+            entropy_bits,
+            strength: strength_label(entropy_bits).to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_survives_a_json_round_trip() {
+        let report = PasswordReport::new(String::from("CW4ngOdZ67hj"), 71.45);
+        let json = serde_json::to_string(&report).unwrap();
+        let parsed: PasswordReport = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(parsed, report);
+        assert_eq!(parsed.password, "CW4ngOdZ67hj");
+        assert_eq!(parsed.length, 12);
+        assert_eq!(parsed.entropy_bits, 71.45);
+        assert_eq!(parsed.strength, "strong");
+    }
+
+    #[test]
+    fn report_length_counts_characters() {
+        assert_eq!(PasswordReport::new(String::from("🐶🐱"), 12.0).length, 2);
+    }
+}
@@ -0,0 +1,99 @@
+/// The rows of a US QWERTY keyboard, unshifted. Keys next to each other in
+/// a row are adjacent.
+const KEYBOARD_ROWS: &[&str] = &[
+    "`1234567890-=",
+    "qwertyuiop[]\\",
+    "asdfghjkl;'",
+    "zxcvbnm,./",
+];
+
+/// Whether `password` has `min_run` or more characters in a row that are
+/// neighbouring keys along one keyboard row, walked in one direction, like
+/// `qwerty`, `asdf` or `lkjh`. Letters match either case.
+pub fn has_keyboard_run(password: &str, min_run: usize) -> bool {
+    has_run(password, min_run, |a, b| {
+        let (a, b) = (a.to_ascii_lowercase(), b.to_ascii_lowercase());
+        KEYBOARD_ROWS.iter().find_map(|row| {
+            let a = row.find(a)?;
+            let b = row.find(b)?;
+            Some(b as isize - a as isize)
+        })
+    })
+}
+
+/// Whether `password` has `min_run` or more letters or digits counting up or
+/// down one at a time, like `abcd`, `4321` or `XyZ`. Letters match either
+/// case and nothing wraps around, so `yza` isn't a run.
+pub fn has_sequential_run(password: &str, min_run: usize) -> bool {
+    has_run(password, min_run, |a, b| {
+        let (a, b) = (a.to_ascii_lowercase(), b.to_ascii_lowercase());
+        let same_kind = (a.is_ascii_lowercase() && b.is_ascii_lowercase())
+            || (a.is_ascii_digit() && b.is_ascii_digit());
+        same_kind.then(|| b as isize - a as isize)
+    })
+}
+
+/// Whether `password` has a run of at least `min_run` characters where
+/// `step` gives the same forward or backward step of one between every
+/// neighbouring pair. `step` returns `None` for pairs that can't be part of
+/// a run at all.
+fn has_run(password: &str, min_run: usize, step: impl Fn(char, char) -> Option<isize>) -> bool {
+    let chars: Vec<char> = password.chars().collect();
+    if min_run <= 1 {
+        return chars.len() >= min_run;
+    }
+
+    let mut run = 1;
+    let mut direction = 0;
+    for pair in chars.windows(2) {
+        match step(pair[0], pair[1]) {
+            Some(next @ (1 | -1)) if run > 1 && next == direction => run += 1,
+            Some(next @ (1 | -1)) => {
+                run = 2;
+                direction = next;
+            }
+            _ => run = 1,
+        }
+        if run >= min_run {
+            return true;
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keyboard_runs_are_found_in_either_direction_and_case() {
+        assert!(has_keyboard_run("asdf", 4));
+        assert!(has_keyboard_run("xQWERTYx", 6));
+        assert!(has_keyboard_run("lkjh", 4));
+        assert!(has_keyboard_run("90-=", 4));
+    }
+
+    #[test]
+    fn strings_without_keyboard_runs() {
+        assert!(!has_keyboard_run("asd", 4));
+        assert!(!has_keyboard_run("aqsw", 3));
+        assert!(!has_keyboard_run("asas", 3));
+        assert!(!has_keyboard_run("CW4ngOdZ67hj", 3));
+    }
+
+    #[test]
+    fn sequential_runs_are_found_going_up_or_down() {
+        assert!(has_sequential_run("abcd", 4));
+        assert!(has_sequential_run("x4321x", 4));
+        assert!(has_sequential_run("XyZ", 3));
+        assert!(has_sequential_run("zyxw", 4));
+    }
+
+    #[test]
+    fn strings_without_sequential_runs() {
+        assert!(!has_sequential_run("abd", 3));
+        assert!(!has_sequential_run("yza", 3));
+        assert!(!has_sequential_run("a1b2c3", 3));
+        assert!(!has_sequential_run("Tr0ub4dor", 3));
+    }
+}
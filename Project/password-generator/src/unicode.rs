@@ -0,0 +1,57 @@
+use crate::{check_length, PasswordError};
+use rand::Rng;
+
+/// 64 emoji for `--emoji`, animals and fruit. Each is a single Unicode
+/// scalar value with no variation selector or joiner, so one pick is always
+/// one `char`.
+pub const EMOJI: &[char] = &[
+    '🐶', '🐱', '🐭', '🐹', '🐰', '🦊', '🐻', '🐼', '🐨', '🐯', '🦁', '🐮', '🐷', '🐸', '🐵', '🐔',
+    '🐧', '🐦', '🐤', '🦆', '🦅', '🦉', '🐺', '🐗', '🐴', '🦄', '🐝', '🐛', '🦋', '🐌', '🐞', '🐜',
+    '🐢', '🐍', '🦎', '🐙', '🦑', '🦀', '🐡', '🐠', '🐟', '🐬', '🐳', '🐋', '🦈', '🐊', '🍎', '🍐',
+    '🍊', '🍋', '🍌', '🍉', '🍇', '🍓', '🍈', '🍒', '🍑', '🍍', '🥝', '🍅', '🥑', '🥕', '🌽', '🥔',
+];
+
+/// Draws `length` characters uniformly from `pool`. Unlike
+/// `generate_password` the pool is `char`s, so it can hold anything
+/// Unicode has; `length` counts characters, not bytes.
+pub fn generate_from_chars<R: Rng + ?Sized>(
+    rng: &mut R,
+    length: usize,
+    pool: &[char],
+) -> Result<String, PasswordError> {
+    if pool.is_empty() {
+        return Err(PasswordError::EmptyCharset);
+    }
+    check_length(length)?;
+
+    Ok((0..length)
+        .map(|_| pool[rng.gen_range(0..pool.len())])
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn length_counts_characters_not_bytes() {
+        let mut rng = StdRng::seed_from_u64(96);
+        for length in [1, 5, 12, 64] {
+            let password = generate_from_chars(&mut rng, length, EMOJI).unwrap();
+
+            assert_eq!(password.chars().count(), length);
+            assert!(password.len() > length);
+            assert!(password.chars().all(|c| EMOJI.contains(&c)), "{password}");
+        }
+    }
+
+    #[test]
+    fn emoji_set_has_no_repeats() {
+        let mut emoji = EMOJI.to_vec();
+        emoji.sort_unstable();
+        emoji.dedup();
+        assert_eq!(emoji.len(), EMOJI.len());
+    }
+}
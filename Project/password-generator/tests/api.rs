@@ -0,0 +1,41 @@
+use password_generator::{build, generate_password, CharClasses, PasswordConfig, PasswordError};
+
+#[test]
+fn build_uses_the_configured_length_and_classes() {
+    let config = PasswordConfig {
+        length: 20,
+        classes: CharClasses {
+            upper: false,
+            lower: true,
+            digits: true,
+            symbols: false,
+        },
+        seed: Some(9),
+        ..PasswordConfig::default()
+    };
+
+    let password = build(&config).unwrap();
+
+    assert_eq!(password.len(), 20);
+    assert!(password
+        .bytes()
+        .all(|byte| byte.is_ascii_lowercase() || byte.is_ascii_digit()));
+}
+
+#[test]
+fn build_reports_errors_through_password_error() {
+    let config = PasswordConfig {
+        classes: CharClasses::none(),
+        ..PasswordConfig::default()
+    };
+
+    assert!(matches!(build(&config), Err(PasswordError::EmptyCharset)));
+}
+
+#[test]
+fn generate_password_is_usable_on_its_own() {
+    let password = generate_password(&mut rand::thread_rng(), 8, b"xy").unwrap();
+
+    assert_eq!(password.len(), 8);
+    assert!(password.chars().all(|c| c == 'x' || c == 'y'));
+}
@@ -1,7 +1,34 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
 fn main() {
-    let mut x = vec![9, 8, 7, 6, 5, 4, 3, 2, 1];
-    let y = exercises1(&mut x);
-    println!("y: {y}");
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    // Numbers come from the CSV file named on the command line, if any.
+    let x = match args.iter().find(|arg| !arg.starts_with("--")) {
+        Some(path) => {
+            let input = std::fs::read_to_string(path).unwrap_or_else(|err| {
+                eprintln!("couldn't read {path}: {err}");
+                std::process::exit(1);
+            });
+            parse_numbers(&input).unwrap_or_else(|err| {
+                eprintln!("{path}: {err}");
+                std::process::exit(1);
+            })
+        }
+        None => vec![9, 8, 7, 6, 5, 4, 3, 2, 1],
+    };
+
+    let report = stats_report(&x);
+    if args.iter().any(|arg| arg == "--json") {
+        println!(
+            "{}",
+            serde_json::to_string(&report).expect("report always serializes")
+        );
+    } else {
+        print!("{report}");
+    }
 }
 
 #[allow(dead_code)]
@@ -157,9 +184,787 @@ fn updating_a_value() {
 }
 
 #[allow(dead_code)]
-fn exercises1(list: &mut Vec<i32>) -> f32 {
+fn exercises1(list: &mut [i32]) -> f32 {
     list.sort();
-    list.iter().sum::<i32>() as f32 / list.len() as f32
+    mean(list).map_or(f32::NAN, |mean| mean as f32)
+}
+
+/// Anything the statistics functions can work on: copyable, comparable and
+/// convertible to `f64` without loss, e.g. `i32`, `u32`, `f32` and `f64`.
+trait Number: Copy + PartialOrd + Into<f64> {}
+
+impl<T: Copy + PartialOrd + Into<f64>> Number for T {}
+
+#[allow(dead_code)]
+fn mean<T: Number>(list: &[T]) -> Option<f64> {
+    if list.is_empty() {
+        return None;
+    }
+    Some(list.iter().map(|&value| value.into()).sum::<f64>() / list.len() as f64)
+}
+
+#[allow(dead_code)]
+fn median<T: Number>(list: &mut [T]) -> Option<f64> {
+    sort_numbers(list);
+    middle(list)
+}
+
+/// The `p`th percentile (`0.0..=100.0`), interpolating linearly between the
+/// two nearest ranks of the sorted list: the rank is `p / 100 * (n - 1)`, so
+/// 0 is the smallest value, 100 the largest and 50 the median. Sorts `list`
+/// in place.
+#[allow(dead_code)]
+fn percentile(list: &mut [i32], p: f64) -> Option<f64> {
+    if list.is_empty() || !(0.0..=100.0).contains(&p) {
+        return None;
+    }
+    list.sort();
+
+    let rank = p / 100.0 * (list.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    let fraction = rank - lower as f64;
+
+    Some(f64::from(list[lower]) + (f64::from(list[upper]) - f64::from(list[lower])) * fraction)
+}
+
+/// `sort` for types that are only `PartialOrd`; NaNs compare as equal.
+#[allow(dead_code)]
+fn sort_numbers<T: Number>(list: &mut [T]) {
+    list.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+}
+
+/// Median of an already sorted list.
+#[allow(dead_code)]
+fn middle<T: Number>(sorted: &[T]) -> Option<f64> {
+    if sorted.is_empty() {
+        return None;
+    }
+
+    let mid = sorted.len() / 2;
+    if sorted.len().is_multiple_of(2) {
+        Some((sorted[mid - 1].into() + sorted[mid].into()) / 2.0)
+    } else {
+        Some(sorted[mid].into())
+    }
+}
+
+/// Population variance: the mean squared distance from the mean, dividing by
+/// `n` rather than `n - 1`.
+#[allow(dead_code)]
+fn variance(list: &[f64]) -> Option<f64> {
+    let average = mean(list)?;
+    let squares: Vec<f64> = list.iter().map(|value| (value - average).powi(2)).collect();
+    mean(&squares)
+}
+
+/// Population standard deviation, the square root of `variance`.
+#[allow(dead_code)]
+fn std_deviation(list: &[f64]) -> Option<f64> {
+    variance(list).map(f64::sqrt)
+}
+
+#[allow(dead_code)]
+#[derive(Debug, PartialEq)]
+enum StatsError {
+    /// Every value needs exactly one weight.
+    LengthMismatch { values: usize, weights: usize },
+    /// The weights sum to zero, including when there are none.
+    ZeroWeights,
+}
+
+impl fmt::Display for StatsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StatsError::LengthMismatch { values, weights } => {
+                write!(f, "{values} values but {weights} weights")
+            }
+            StatsError::ZeroWeights => write!(f, "the weights add up to zero"),
+        }
+    }
+}
+
+impl std::error::Error for StatsError {}
+
+/// `sum(value * weight) / sum(weight)`.
+#[allow(dead_code)]
+fn weighted_mean(values: &[f64], weights: &[f64]) -> Result<f64, StatsError> {
+    if values.len() != weights.len() {
+        return Err(StatsError::LengthMismatch {
+            values: values.len(),
+            weights: weights.len(),
+        });
+    }
+
+    let total_weight: f64 = weights.iter().sum();
+    if total_weight == 0.0 {
+        return Err(StatsError::ZeroWeights);
+    }
+
+    let weighted_sum: f64 = values
+        .iter()
+        .zip(weights)
+        .map(|(value, weight)| value * weight)
+        .sum();
+    Ok(weighted_sum / total_weight)
+}
+
+/// Smallest and largest value, found in a single pass.
+#[allow(dead_code)]
+fn min_max(list: &[i32]) -> Option<(i32, i32)> {
+    let (&first, rest) = list.split_first()?;
+    Some(rest.iter().fold((first, first), |(min, max), &value| {
+        (min.min(value), max.max(value))
+    }))
+}
+
+/// Most frequent value in `list`; on a tie the smallest value wins.
+#[allow(dead_code)]
+fn mode(list: &[i32]) -> Option<i32> {
+    histogram(list)
+        .into_iter()
+        .max_by(|(a, a_count), (b, b_count)| a_count.cmp(b_count).then(b.cmp(a)))
+        .map(|(value, _)| value)
+}
+
+/// How many times each value appears in `values`.
+#[allow(dead_code)]
+fn histogram(values: &[i32]) -> HashMap<i32, usize> {
+    let mut hist = HashMap::new();
+    for &value in values {
+        *hist.entry(value).or_insert(0) += 1;
+    }
+    hist
+}
+
+/// One line per value, smallest first, with a `*` per occurrence, e.g.
+/// `3: ***`.
+#[allow(dead_code)]
+fn render_histogram(hist: &HashMap<i32, usize>) -> String {
+    let mut values: Vec<_> = hist.iter().collect();
+    values.sort();
+
+    values
+        .into_iter()
+        .map(|(value, &count)| format!("{value}: {}\n", "*".repeat(count)))
+        .collect()
+}
+
+#[allow(dead_code)]
+fn print_histogram(hist: &HashMap<i32, usize>) {
+    print!("{}", render_histogram(hist));
+}
+
+/// The biggest element of `list`, the generic `largest` from the book.
+#[allow(dead_code)]
+fn largest<T: PartialOrd + Copy>(list: &[T]) -> Option<T> {
+    let mut iter = list.iter().copied();
+    let mut largest = iter.next()?;
+
+    for item in iter {
+        if item > largest {
+            largest = item;
+        }
+    }
+
+    Some(largest)
+}
+
+/// `list` without repeated values, each kept where it first appeared. Unlike
+/// `Vec::dedup` this also drops duplicates that aren't next to each other.
+#[allow(dead_code)]
+fn dedup_preserve_order(list: &[i32]) -> Vec<i32> {
+    let mut seen = HashSet::new();
+    list.iter()
+        .copied()
+        .filter(|&value| seen.insert(value))
+        .collect()
+}
+
+/// Vowels `to_pig_latin` recognises: Latin and, for words like the chapter's
+/// `Здравствуйте`, Cyrillic. In any other script every letter counts as a
+/// consonant.
+const VOWELS: &str = "aeiouAEIOUаеёиоуыэюяАЕЁИОУЫЭЮЯ";
+
+/// Pig latin, one word at a time: `first` becomes `irst-fay` and words that
+/// start with a vowel keep it and get `-hay`, so `apple` becomes `apple-hay`.
+/// Works on `char`s rather than bytes so non-ASCII words aren't split.
+#[allow(dead_code)]
+fn to_pig_latin(text: &str) -> String {
+    text.split_whitespace()
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) if VOWELS.contains(first) => format!("{word}-hay"),
+                Some(first) => format!("{}-{first}ay", chars.as_str()),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// How many times each whitespace-separated word appears in `text`.
+#[allow(dead_code)]
+fn word_frequencies(text: &str) -> HashMap<String, usize> {
+    let mut map = HashMap::new();
+
+    for word in text.split_whitespace() {
+        let count = map.entry(word.to_string()).or_insert(0);
+        *count += 1;
+    }
+
+    map
+}
+
+/// The `n` most frequent words, most frequent first; words with the same
+/// count are in alphabetical order.
+#[allow(dead_code)]
+fn top_n_words(text: &str, n: usize) -> Vec<(String, usize)> {
+    let mut words: Vec<(String, usize)> = word_frequencies(text).into_iter().collect();
+    words.sort_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then(a.cmp(b)));
+    words.truncate(n);
+    words
 }
 
+/// Mean, median and mode of a list, kept together. `from_slice` works on a
+/// sorted copy so the caller's data is left untouched.
 #[allow(dead_code)]
+struct Statistics<T> {
+    sorted: Vec<T>,
+}
+
+#[allow(dead_code)]
+impl<T: Number> Statistics<T> {
+    fn from_slice(list: &[T]) -> Statistics<T> {
+        let mut sorted = list.to_vec();
+        sort_numbers(&mut sorted);
+        Statistics { sorted }
+    }
+
+    fn mean(&self) -> Option<f64> {
+        mean(&self.sorted)
+    }
+
+    fn median(&self) -> Option<f64> {
+        middle(&self.sorted)
+    }
+}
+
+/// Mode needs exact equality to count values, so it's only offered for
+/// integers.
+#[allow(dead_code)]
+impl Statistics<i32> {
+    fn mode(&self) -> Option<i32> {
+        mode(&self.sorted)
+    }
+}
+
+/// A mean updated one value at a time, for input too big to keep in a
+/// `Vec`. Uses Welford's update, `mean += (value - mean) / count`, which
+/// doesn't need the running sum and so can't overflow it.
+#[allow(dead_code)]
+#[derive(Debug, Default)]
+struct RunningMean {
+    count: u64,
+    mean: f64,
+}
+
+#[allow(dead_code)]
+impl RunningMean {
+    fn push(&mut self, value: f64) {
+        self.count += 1;
+        self.mean += (value - self.mean) / self.count as f64;
+    }
+
+    /// `NaN` until the first value is pushed, like `exercises1`.
+    fn mean(&self) -> f64 {
+        if self.count == 0 {
+            f64::NAN
+        } else {
+            self.mean
+        }
+    }
+}
+
+/// A value in the input that isn't an integer.
+#[derive(Debug, PartialEq)]
+struct ParseError {
+    /// 1-based line number.
+    line: usize,
+    value: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: `{}` is not a number", self.line, self.value)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Reads integers written one per line, comma-separated, or a mix of both.
+/// Blank lines and spaces around values are skipped.
+fn parse_numbers(input: &str) -> Result<Vec<i32>, ParseError> {
+    let mut numbers = Vec::new();
+
+    for (index, line) in input.lines().enumerate() {
+        for value in line
+            .split(',')
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+        {
+            let number = value.parse().map_err(|_| ParseError {
+                line: index + 1,
+                value: value.to_string(),
+            })?;
+            numbers.push(number);
+        }
+    }
+
+    Ok(numbers)
+}
+
+/// Summary statistics of a list, printed as text or, with `--json`, as
+/// JSON. Every field but `count` is `None` for an empty list.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct StatsReport {
+    count: usize,
+    mean: Option<f64>,
+    median: Option<f64>,
+    mode: Option<i32>,
+    min: Option<i32>,
+    max: Option<i32>,
+    std_deviation: Option<f64>,
+}
+
+/// One `name: value` line per field, fractions to two decimal places and
+/// `-` for a missing value.
+impl fmt::Display for StatsReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn whole(value: Option<i32>) -> String {
+            value.map_or_else(|| String::from("-"), |value| value.to_string())
+        }
+        fn fraction(value: Option<f64>) -> String {
+            value.map_or_else(|| String::from("-"), |value| format!("{value:.2}"))
+        }
+
+        writeln!(f, "count: {}", self.count)?;
+        writeln!(f, "mean: {}", fraction(self.mean))?;
+        writeln!(f, "median: {}", fraction(self.median))?;
+        writeln!(f, "mode: {}", whole(self.mode))?;
+        writeln!(f, "min: {}", whole(self.min))?;
+        writeln!(f, "max: {}", whole(self.max))?;
+        writeln!(f, "std deviation: {}", fraction(self.std_deviation))
+    }
+}
+
+fn stats_report(list: &[i32]) -> StatsReport {
+    let stats = Statistics::from_slice(list);
+    let (min, max) = match min_max(list) {
+        Some((min, max)) => (Some(min), Some(max)),
+        None => (None, None),
+    };
+
+    StatsReport {
+        count: list.len(),
+        mean: stats.mean(),
+        median: stats.median(),
+        mode: stats.mode(),
+        min,
+        max,
+        std_deviation: std_deviation(
+            &list
+                .iter()
+                .map(|&value| f64::from(value))
+                .collect::<Vec<_>>(),
+        ),
+    }
+}
+
+/// Company directory from the chapter's HashMap exercise: employees grouped
+/// by department.
+#[allow(dead_code)]
+#[derive(Debug, Default)]
+struct Company {
+    departments: HashMap<String, Vec<String>>,
+}
+
+#[allow(dead_code)]
+impl Company {
+    /// "Add Sally to Engineering" becomes `add_employee("Sally", "Engineering")`.
+    fn add_employee(&mut self, name: &str, department: &str) {
+        self.departments
+            .entry(department.to_string())
+            .or_default()
+            .push(name.to_string());
+    }
+
+    /// Everyone in `department`, sorted alphabetically.
+    fn list_department(&self, department: &str) -> Vec<String> {
+        let mut employees = self
+            .departments
+            .get(department)
+            .cloned()
+            .unwrap_or_default();
+        employees.sort();
+        employees
+    }
+
+    /// Every department with its employees, both sorted alphabetically.
+    fn list_all(&self) -> Vec<(String, Vec<String>)> {
+        let mut all: Vec<(String, Vec<String>)> = self
+            .departments
+            .keys()
+            .map(|department| (department.clone(), self.list_department(department)))
+            .collect();
+        all.sort();
+        all
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pig_latin_moves_a_leading_consonant() {
+        assert_eq!(to_pig_latin("first"), "irst-fay");
+        assert_eq!(to_pig_latin("first second"), "irst-fay econd-say");
+    }
+
+    #[test]
+    fn pig_latin_keeps_a_leading_vowel() {
+        assert_eq!(to_pig_latin("apple"), "apple-hay");
+        assert_eq!(to_pig_latin("Apple"), "Apple-hay");
+    }
+
+    #[test]
+    fn pig_latin_handles_cyrillic() {
+        assert_eq!(to_pig_latin("Здравствуйте"), "дравствуйте-Зay");
+        assert_eq!(to_pig_latin("утро"), "утро-hay");
+    }
+
+    #[test]
+    fn median_of_odd_length_is_the_middle_value() {
+        assert_eq!(median(&mut [3, 1, 2]), Some(2.0));
+    }
+
+    #[test]
+    fn median_of_even_length_averages_the_middle_two() {
+        assert_eq!(median(&mut [4, 1, 3, 2]), Some(2.5));
+    }
+
+    #[test]
+    fn median_of_one_value_is_that_value() {
+        assert_eq!(median(&mut [7]), Some(7.0));
+    }
+
+    #[test]
+    fn median_of_nothing_is_none() {
+        assert_eq!(median::<i32>(&mut []), None);
+    }
+
+    #[test]
+    fn mode_is_the_most_frequent_value() {
+        assert_eq!(mode(&[1, 3, 3, 2, 3, 1]), Some(3));
+    }
+
+    #[test]
+    fn mode_tie_goes_to_the_smallest_value() {
+        assert_eq!(mode(&[5, 2, 5, 2, 9]), Some(2));
+    }
+
+    #[test]
+    fn mode_of_nothing_is_none() {
+        assert_eq!(mode(&[]), None);
+    }
+
+    #[test]
+    fn statistics_share_one_sorted_copy() {
+        let list = vec![4, 1, 4, 2, 9];
+        let stats = Statistics::from_slice(&list);
+
+        assert_eq!(stats.mean(), Some(4.0));
+        assert_eq!(stats.median(), Some(4.0));
+        assert_eq!(stats.mode(), Some(4));
+        assert_eq!(list, [4, 1, 4, 2, 9]);
+    }
+
+    #[test]
+    fn statistics_work_on_integers_and_floats() {
+        assert_eq!(mean(&[1, 2, 3, 4]), Some(2.5));
+        assert_eq!(median(&mut [3, 1, 2]), Some(2.0));
+
+        assert_eq!(mean(&[1.5, 2.5]), Some(2.0));
+        assert_eq!(median(&mut [2.5, 0.5, 1.5, 3.5]), Some(2.0));
+        assert_eq!(Statistics::from_slice(&[3.0_f64, 1.0]).median(), Some(2.0));
+        assert_eq!(mean::<f64>(&[]), None);
+    }
+
+    #[test]
+    fn word_frequencies_count_each_word() {
+        let counts = word_frequencies("the cat and the hat and the bat");
+
+        assert_eq!(counts["the"], 3);
+        assert_eq!(counts["and"], 2);
+        assert_eq!(counts["cat"], 1);
+        assert_eq!(counts.len(), 5);
+    }
+
+    #[test]
+    fn top_words_are_most_frequent_first_then_alphabetical() {
+        let top = top_n_words("the cat and the hat and the bat", 4);
+
+        assert_eq!(
+            top,
+            [
+                (String::from("the"), 3),
+                (String::from("and"), 2),
+                (String::from("bat"), 1),
+                (String::from("cat"), 1),
+            ]
+        );
+        assert_eq!(top_n_words("one", 5).len(), 1);
+    }
+
+    #[test]
+    fn company_lists_each_department_sorted() {
+        let mut company = Company::default();
+        company.add_employee("Sally", "Engineering");
+        company.add_employee("Amir", "Sales");
+        company.add_employee("Bob", "Engineering");
+
+        assert_eq!(company.list_department("Engineering"), ["Bob", "Sally"]);
+        assert_eq!(company.list_department("Sales"), ["Amir"]);
+        assert!(company.list_department("Marketing").is_empty());
+    }
+
+    #[test]
+    fn company_lists_everyone_by_department() {
+        let mut company = Company::default();
+        company.add_employee("Sally", "Engineering");
+        company.add_employee("Amir", "Sales");
+        company.add_employee("Bob", "Engineering");
+        company.add_employee("Zoe", "Accounts");
+
+        assert_eq!(
+            company.list_all(),
+            [
+                (String::from("Accounts"), vec![String::from("Zoe")]),
+                (
+                    String::from("Engineering"),
+                    vec![String::from("Bob"), String::from("Sally")]
+                ),
+                (String::from("Sales"), vec![String::from("Amir")]),
+            ]
+        );
+    }
+
+    #[test]
+    fn largest_works_for_numbers_and_chars() {
+        assert_eq!(largest(&[34, 50, 25, 100, 65]), Some(100));
+        assert_eq!(largest(&['y', 'm', 'a', 'q']), Some('y'));
+    }
+
+    #[test]
+    fn largest_of_nothing_is_none() {
+        assert_eq!(largest::<i32>(&[]), None);
+    }
+
+    #[test]
+    fn variance_and_std_deviation_match_hand_computed_values() {
+        // Mean 5; squared distances 9, 1, 1, 1, 0, 0, 4, 16 average to 4.
+        let list = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+
+        assert_eq!(variance(&list), Some(4.0));
+        assert_eq!(std_deviation(&list), Some(2.0));
+        assert_eq!(variance(&[3.0]), Some(0.0));
+        assert_eq!(variance(&[]), None);
+    }
+
+    #[test]
+    fn min_max_finds_both_ends() {
+        assert_eq!(min_max(&[3, -7, 12, 0, 5]), Some((-7, 12)));
+    }
+
+    #[test]
+    fn min_max_of_one_value() {
+        assert_eq!(min_max(&[4]), Some((4, 4)));
+    }
+
+    #[test]
+    fn min_max_of_nothing_is_none() {
+        assert_eq!(min_max(&[]), None);
+    }
+
+    #[test]
+    fn dedup_preserve_order_drops_repeats_vec_dedup_misses() {
+        let list = [3, 1, 3, 2, 1, 1];
+        let mut adjacent_only = list.to_vec();
+        adjacent_only.dedup();
+
+        assert_eq!(adjacent_only, [3, 1, 3, 2, 1]);
+        assert_eq!(dedup_preserve_order(&list), [3, 1, 2]);
+    }
+
+    #[test]
+    fn histogram_counts_each_value() {
+        let hist = histogram(&[3, 1, 3, 2, 3]);
+
+        assert_eq!(hist, HashMap::from([(1, 1), (2, 1), (3, 3)]));
+    }
+
+    #[test]
+    fn rendered_histogram_is_sorted_with_a_star_per_occurrence() {
+        assert_eq!(
+            render_histogram(&histogram(&[3, 1, 3, 2, 3])),
+            "1: *\n2: *\n3: ***\n"
+        );
+        assert_eq!(render_histogram(&HashMap::new()), "");
+    }
+
+    #[test]
+    fn stats_report_round_trips_through_json() {
+        let report = stats_report(&[5, 1, 2, 2]);
+        assert_eq!(
+            report,
+            StatsReport {
+                count: 4,
+                mean: Some(2.5),
+                median: Some(2.0),
+                mode: Some(2),
+                min: Some(1),
+                max: Some(5),
+                std_deviation: Some(1.5),
+            }
+        );
+
+        let json = serde_json::to_string(&report).unwrap();
+        assert_eq!(serde_json::from_str::<StatsReport>(&json).unwrap(), report);
+    }
+
+    #[test]
+    fn empty_stats_report_serializes_nulls() {
+        let json = serde_json::to_string(&stats_report(&[])).unwrap();
+
+        assert_eq!(
+            json,
+            r#"{"count":0,"mean":null,"median":null,"mode":null,"min":null,"max":null,"std_deviation":null}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<StatsReport>(&json).unwrap(),
+            stats_report(&[])
+        );
+    }
+
+    #[test]
+    fn parse_numbers_reads_one_per_line() {
+        assert_eq!(parse_numbers("3\n-1\n\n  42  \n"), Ok(vec![3, -1, 42]));
+    }
+
+    #[test]
+    fn parse_numbers_reads_comma_separated_values() {
+        assert_eq!(parse_numbers("1, 2,3 ,\n4,5"), Ok(vec![1, 2, 3, 4, 5]));
+    }
+
+    #[test]
+    fn parse_numbers_reports_the_line_of_a_bad_value() {
+        assert_eq!(
+            parse_numbers("1\n2, 3\n4, five\n6"),
+            Err(ParseError {
+                line: 3,
+                value: "five".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn running_mean_matches_the_batch_mean() {
+        let list = [2.5, -1.0, 7.25, 3.0, 10.0, 0.5];
+        let mut running = RunningMean::default();
+        for &value in &list {
+            running.push(value);
+        }
+
+        assert!((running.mean() - mean(&list).unwrap()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn running_mean_of_nothing_is_nan() {
+        assert!(RunningMean::default().mean().is_nan());
+    }
+
+    #[test]
+    fn weighted_mean_with_equal_weights_is_the_mean() {
+        let values = [1.0, 4.0, 10.0];
+
+        assert_eq!(weighted_mean(&values, &[2.0, 2.0, 2.0]), Ok(5.0));
+        assert_eq!(mean(&values), Some(5.0));
+    }
+
+    #[test]
+    fn weighted_mean_favours_heavier_values() {
+        // (1 * 1 + 4 * 3) / (1 + 3)
+        assert_eq!(weighted_mean(&[1.0, 4.0], &[1.0, 3.0]), Ok(3.25));
+    }
+
+    #[test]
+    fn weighted_mean_rejects_mismatched_lengths() {
+        assert_eq!(
+            weighted_mean(&[1.0, 2.0, 3.0], &[1.0, 1.0]),
+            Err(StatsError::LengthMismatch {
+                values: 3,
+                weights: 2,
+            })
+        );
+    }
+
+    #[test]
+    fn weighted_mean_rejects_zero_weights() {
+        assert_eq!(
+            weighted_mean(&[1.0, 2.0], &[0.0, 0.0]),
+            Err(StatsError::ZeroWeights)
+        );
+        assert_eq!(weighted_mean(&[], &[]), Err(StatsError::ZeroWeights));
+    }
+
+    #[test]
+    fn percentile_ends_are_the_minimum_and_maximum() {
+        let mut list = [40, 10, 30, 20];
+
+        assert_eq!(percentile(&mut list, 0.0), Some(10.0));
+        assert_eq!(percentile(&mut list, 100.0), Some(40.0));
+    }
+
+    #[test]
+    fn fiftieth_percentile_is_the_median() {
+        let mut odd = [7, 1, 3];
+        let mut even = [40, 10, 30, 20];
+
+        assert_eq!(percentile(&mut odd, 50.0), median(&mut [7, 1, 3]));
+        assert_eq!(percentile(&mut even, 50.0), Some(25.0));
+        assert_eq!(median(&mut even), Some(25.0));
+    }
+
+    #[test]
+    fn percentile_outside_zero_to_hundred_is_none() {
+        let mut list = [1, 2, 3];
+
+        assert_eq!(percentile(&mut list, -1.0), None);
+        assert_eq!(percentile(&mut list, 100.5), None);
+        assert_eq!(percentile(&mut [], 50.0), None);
+    }
+
+    #[test]
+    fn stats_report_prints_every_field() {
+        assert_eq!(
+            stats_report(&[5, 1, 2, 2]).to_string(),
+            "count: 4\nmean: 2.50\nmedian: 2.00\nmode: 2\nmin: 1\nmax: 5\nstd deviation: 1.50\n"
+        );
+        assert_eq!(
+            stats_report(&[]).to_string(),
+            "count: 0\nmean: -\nmedian: -\nmode: -\nmin: -\nmax: -\nstd deviation: -\n"
+        );
+    }
+}
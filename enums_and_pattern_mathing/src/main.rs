@@ -1,24 +1,158 @@
+use std::collections::HashMap;
+
+#[allow(dead_code)]
 #[derive(Debug)]
 enum UsState {
-    Albama,
+    Alabama,
     Alaska,
+    Arizona,
+    California,
+    Colorado,
+    Florida,
+    NewYork,
+    Texas,
     // etc
 }
+
+impl UsState {
+    fn name(&self) -> &'static str {
+        match self {
+            UsState::Alabama => "Alabama",
+            UsState::Alaska => "Alaska",
+            UsState::Arizona => "Arizona",
+            UsState::California => "California",
+            UsState::Colorado => "Colorado",
+            UsState::Florida => "Florida",
+            UsState::NewYork => "New York",
+            UsState::Texas => "Texas",
+        }
+    }
+}
+
 enum Coin {
     Penny,
     Nickel,
     Dime,
     Quarter(UsState),
 }
+
+impl Coin {
+    fn value_in_cents(&self) -> u32 {
+        match self {
+            Coin::Penny => 1,
+            Coin::Nickel => 5,
+            Coin::Dime => 10,
+            Coin::Quarter(_) => 25,
+        }
+    }
+}
+
+fn total_cents(coins: &[Coin]) -> u32 {
+    coins.iter().map(Coin::value_in_cents).sum()
+}
+
+/// How many quarters came from each state, keyed by the state's name.
+fn quarters_by_state(coins: &[Coin]) -> HashMap<String, usize> {
+    let mut counts = HashMap::new();
+    for coin in coins {
+        if let Coin::Quarter(state) = coin {
+            *counts.entry(state.name().to_string()).or_insert(0) += 1;
+        }
+    }
+    counts
+}
+
 fn main() {
     let mut count = 0;
-    let coin = Coin::Quarter(UsState::Albama);
+    let coin = Coin::Quarter(UsState::Alabama);
 
     if let Coin::Quarter(state) = coin {
-        println!("State quarter from {:?}!", state);
+        println!("State quarter from {}!", state.name());
     } else {
         count += 1;
     }
 
     println!("Count : {}", count);
+
+    let coins = [
+        Coin::Penny,
+        Coin::Nickel,
+        Coin::Dime,
+        Coin::Quarter(UsState::Alaska),
+    ];
+    for coin in &coins {
+        println!("{} cents", coin.value_in_cents());
+    }
+    println!("Total : {} cents", total_cents(&coins));
+
+    let collection = [
+        Coin::Quarter(UsState::Texas),
+        Coin::Dime,
+        Coin::Quarter(UsState::Florida),
+        Coin::Quarter(UsState::Texas),
+    ];
+    for (state, quarters) in quarters_by_state(&collection) {
+        println!("{} : {}", state, quarters);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn penny_is_one_cent() {
+        assert_eq!(Coin::Penny.value_in_cents(), 1);
+    }
+
+    #[test]
+    fn nickel_is_five_cents() {
+        assert_eq!(Coin::Nickel.value_in_cents(), 5);
+    }
+
+    #[test]
+    fn dime_is_ten_cents() {
+        assert_eq!(Coin::Dime.value_in_cents(), 10);
+    }
+
+    #[test]
+    fn quarter_is_twenty_five_cents_from_any_state() {
+        assert_eq!(Coin::Quarter(UsState::Texas).value_in_cents(), 25);
+        assert_eq!(Coin::Quarter(UsState::NewYork).value_in_cents(), 25);
+    }
+
+    #[test]
+    fn total_cents_adds_up_mixed_coins() {
+        let coins = [
+            Coin::Penny,
+            Coin::Nickel,
+            Coin::Dime,
+            Coin::Quarter(UsState::Alaska),
+            Coin::Penny,
+        ];
+
+        assert_eq!(total_cents(&coins), 42);
+        assert_eq!(total_cents(&[]), 0);
+    }
+
+    #[test]
+    fn state_names_read_like_prose() {
+        assert_eq!(UsState::Alabama.name(), "Alabama");
+        assert_eq!(UsState::NewYork.name(), "New York");
+    }
+
+    #[test]
+    fn quarters_by_state_counts_only_quarters() {
+        let coins = [
+            Coin::Quarter(UsState::Alaska),
+            Coin::Dime,
+            Coin::Quarter(UsState::Texas),
+            Coin::Quarter(UsState::Alaska),
+        ];
+
+        assert_eq!(
+            quarters_by_state(&coins),
+            HashMap::from([(String::from("Alaska"), 2), (String::from("Texas"), 1)])
+        );
+    }
 }
@@ -1,12 +1,18 @@
-use std::fmt::Debug;
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, ErrorKind, Read};
+use std::thread;
+use std::time::Duration;
 
+#[derive(Debug)]
 pub struct Guess {
     value: i32,
 }
 
 impl Guess {
     pub fn new(value: i32) -> Guess {
-        if value < 1 || value > 100 {
+        if !(1..=100).contains(&value) {
             panic!("Guess value must be between 1 and 100, got {}", value);
         }
 
@@ -18,4 +24,202 @@ impl Guess {
     }
 }
 
-impl Guess for Debug {}
+#[derive(Debug)]
+pub enum AppError {
+    /// The path that couldn't be found.
+    FileNotFound(String),
+    Io(io::Error),
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AppError::FileNotFound(path) => write!(f, "file not found: {}", path),
+            AppError::Io(err) => write!(f, "I/O error: {}", err),
+        }
+    }
+}
+
+impl Error for AppError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AppError::FileNotFound(_) => None,
+            AppError::Io(err) => Some(err),
+        }
+    }
+}
+
+impl From<io::Error> for AppError {
+    fn from(err: io::Error) -> Self {
+        AppError::Io(err)
+    }
+}
+
+/// Opens `path`, turning a missing file into `AppError::FileNotFound` so the
+/// caller knows which path it was.
+pub fn open_file(path: &str) -> Result<File, AppError> {
+    match File::open(path) {
+        Ok(file) => Ok(file),
+        Err(err) if err.kind() == ErrorKind::NotFound => {
+            Err(AppError::FileNotFound(path.to_string()))
+        }
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Opens `path`, creating an empty file there if it doesn't exist yet. Any
+/// error other than `NotFound` is passed through unchanged.
+pub fn open_or_create(path: &str) -> Result<File, io::Error> {
+    match File::open(path) {
+        Ok(file) => Ok(file),
+        Err(err) if err.kind() == ErrorKind::NotFound => File::create(path),
+        Err(err) => Err(err),
+    }
+}
+
+/// How long `open_with_retries` waits between attempts.
+pub const RETRY_DELAY: Duration = Duration::from_millis(100);
+
+/// Tries to open `path`, then up to `retries` more times with `RETRY_DELAY`
+/// between attempts, for files that are briefly locked or not written yet.
+/// Gives back the last error if every attempt fails.
+pub fn open_with_retries(path: &str, retries: u32) -> Result<File, io::Error> {
+    let mut attempts_left = retries;
+    loop {
+        match File::open(path) {
+            Ok(file) => return Ok(file),
+            Err(err) if attempts_left == 0 => return Err(err),
+            Err(_) => {
+                attempts_left -= 1;
+                thread::sleep(RETRY_DELAY);
+            }
+        }
+    }
+}
+
+/// The first line of the file at `path`, trimmed.
+///
+/// `?` returns early with the error, the same as writing the match out by
+/// hand:
+///
+/// ```text
+/// let mut file = match File::open(path) {
+///     Ok(file) => file,
+///     Err(e) => return Err(e),
+/// };
+///
+/// let mut contents = String::new();
+/// match file.read_to_string(&mut contents) {
+///     Ok(_) => {}
+///     Err(e) => return Err(e),
+/// }
+/// ```
+pub fn read_username_from_file(path: &str) -> Result<String, io::Error> {
+    let mut contents = String::new();
+    File::open(path)?.read_to_string(&mut contents)?;
+
+    Ok(contents.lines().next().unwrap_or("").trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::PathBuf;
+
+    /// A path in the temp dir that's unique to this test run.
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("error-handling-{}-{name}", std::process::id()))
+    }
+
+    #[test]
+    fn open_file_reports_the_missing_path() {
+        let path = temp_path("missing.txt");
+        let path = path.to_str().unwrap();
+
+        match open_file(path) {
+            Err(AppError::FileNotFound(missing)) => assert_eq!(missing, path),
+            other => panic!("expected FileNotFound, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn read_username_returns_the_trimmed_first_line() {
+        let path = temp_path("username.txt");
+        fs::write(&path, "  yusril  \nsecond line\n").unwrap();
+
+        let username = read_username_from_file(path.to_str().unwrap());
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(username.unwrap(), "yusril");
+    }
+
+    #[test]
+    fn read_username_from_a_missing_file_is_not_found() {
+        let path = temp_path("no-username.txt");
+
+        let err = read_username_from_file(path.to_str().unwrap()).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn open_with_retries_waits_for_a_file_written_late() {
+        let path = temp_path("late.txt");
+        let writer = {
+            let path = path.clone();
+            thread::spawn(move || {
+                // Lands after the first attempt but well before the last.
+                thread::sleep(RETRY_DELAY / 2);
+                fs::write(path, "ready").unwrap();
+            })
+        };
+
+        let file = open_with_retries(path.to_str().unwrap(), 10);
+        writer.join().unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert!(file.is_ok());
+    }
+
+    #[test]
+    fn open_with_retries_gives_up_on_a_file_that_never_appears() {
+        let path = temp_path("never.txt");
+        let started = std::time::Instant::now();
+
+        let err = open_with_retries(path.to_str().unwrap(), 2).unwrap_err();
+
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+        assert!(started.elapsed() >= RETRY_DELAY * 2);
+    }
+
+    #[test]
+    fn open_or_create_creates_a_missing_file() {
+        let dir = temp_path("create");
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("new.txt");
+
+        let file = open_or_create(path.to_str().unwrap());
+        let exists = path.exists();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(file.is_ok());
+        assert!(exists);
+    }
+
+    #[test]
+    fn open_or_create_opens_an_existing_file_untouched() {
+        let dir = temp_path("open");
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("existing.txt");
+        fs::write(&path, "keep me").unwrap();
+
+        let mut contents = String::new();
+        open_or_create(path.to_str().unwrap())
+            .unwrap()
+            .read_to_string(&mut contents)
+            .unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(contents, "keep me");
+    }
+}
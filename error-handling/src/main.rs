@@ -1,7 +1,29 @@
-use error_handling::Guess;
+use error_handling::{
+    open_file, open_or_create, open_with_retries, read_username_from_file, AppError, Guess,
+};
+use std::io::Read;
 
-fn main() {
-    let test = Guess { value: 4 };
+fn main() -> Result<(), AppError> {
+    let test = Guess::new(4);
 
     println!("{:#?}", test);
+
+    let mut contents = String::new();
+    open_file("hello.txt")?.read_to_string(&mut contents)?;
+    print!("{}", contents);
+
+    println!("username : {}", read_username_from_file("hello.txt")?);
+
+    if let Err(err) = open_file("missing.txt") {
+        println!("{}", err);
+    }
+
+    if let Err(err) = open_with_retries("missing.txt", 2) {
+        println!("gave up after retrying : {}", err);
+    }
+
+    open_or_create("hello.txt")?;
+    println!("hello.txt is still there");
+
+    Ok(())
 }
@@ -0,0 +1,163 @@
+use std::cmp::Ordering;
+use std::ops::RangeInclusive;
+
+/// What a single guess did to the round.
+#[derive(Debug, PartialEq)]
+pub enum GuessOutcome {
+    TooLow,
+    TooHigh,
+    Correct,
+    /// Outside the range; doesn't count as an attempt.
+    OutOfRange,
+    /// Wrong, and that was the last attempt, or the attempts were already
+    /// used up.
+    OutOfAttempts,
+}
+
+/// One round of the guessing game, with no I/O, so `main` only has to read
+/// input and print outcomes.
+pub struct Game {
+    secret: u32,
+    range: RangeInclusive<u32>,
+    max_attempts: u32,
+    guesses: u32,
+}
+
+impl Game {
+    pub fn new(secret: u32, range: RangeInclusive<u32>, max_attempts: u32) -> Game {
+        Game {
+            secret,
+            range,
+            max_attempts,
+            guesses: 0,
+        }
+    }
+
+    pub fn guess(&mut self, n: u32) -> GuessOutcome {
+        if out_of_attempts(self.guesses, self.max_attempts) {
+            return GuessOutcome::OutOfAttempts;
+        }
+        if !self.range.contains(&n) {
+            return GuessOutcome::OutOfRange;
+        }
+
+        self.guesses += 1;
+        let outcome = match n.cmp(&self.secret) {
+            Ordering::Less => GuessOutcome::TooLow,
+            Ordering::Greater => GuessOutcome::TooHigh,
+            Ordering::Equal => return GuessOutcome::Correct,
+        };
+
+        if out_of_attempts(self.guesses, self.max_attempts) {
+            GuessOutcome::OutOfAttempts
+        } else {
+            outcome
+        }
+    }
+
+    pub fn secret(&self) -> u32 {
+        self.secret
+    }
+
+    /// Guesses counted so far; out-of-range ones aren't.
+    pub fn guesses(&self) -> u32 {
+        self.guesses
+    }
+}
+
+/// Whether `guesses` have used up the allowance.
+fn out_of_attempts(guesses: u32, max_attempts: u32) -> bool {
+    guesses >= max_attempts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn correct_guess_wins() {
+        let mut game = Game::new(7, 1..=10, 3);
+
+        assert_eq!(game.guess(7), GuessOutcome::Correct);
+        assert_eq!(game.guesses(), 1);
+    }
+
+    #[test]
+    fn wrong_guesses_say_which_way() {
+        let mut game = Game::new(7, 1..=10, 3);
+
+        assert_eq!(game.guess(3), GuessOutcome::TooLow);
+        assert_eq!(game.guess(9), GuessOutcome::TooHigh);
+    }
+
+    #[test]
+    fn last_wrong_guess_loses() {
+        let mut game = Game::new(7, 1..=10, 2);
+
+        assert_eq!(game.guess(3), GuessOutcome::TooLow);
+        assert_eq!(game.guess(9), GuessOutcome::OutOfAttempts);
+    }
+
+    #[test]
+    fn correct_last_guess_still_wins() {
+        let mut game = Game::new(7, 1..=10, 2);
+
+        assert_eq!(game.guess(3), GuessOutcome::TooLow);
+        assert_eq!(game.guess(7), GuessOutcome::Correct);
+    }
+
+    #[test]
+    fn winning_round_narrows_in_on_the_secret() {
+        let mut game = Game::new(37, 1..=100, 10);
+        let outcomes: Vec<_> = [50, 25, 150, 37].map(|n| game.guess(n)).into();
+
+        assert_eq!(
+            outcomes,
+            [
+                GuessOutcome::TooHigh,
+                GuessOutcome::TooLow,
+                GuessOutcome::OutOfRange,
+                GuessOutcome::Correct,
+            ]
+        );
+        assert_eq!(game.guesses(), 3);
+    }
+
+    #[test]
+    fn losing_round_uses_every_attempt() {
+        let mut game = Game::new(37, 1..=100, 3);
+        let outcomes: Vec<_> = [10, 0, 90, 20].map(|n| game.guess(n)).into();
+
+        assert_eq!(
+            outcomes,
+            [
+                GuessOutcome::TooLow,
+                GuessOutcome::OutOfRange,
+                GuessOutcome::TooHigh,
+                GuessOutcome::OutOfAttempts,
+            ]
+        );
+        assert_eq!(game.guesses(), 3);
+        assert_eq!(game.secret(), 37);
+    }
+
+    #[test]
+    fn guesses_after_losing_are_ignored() {
+        let mut game = Game::new(37, 1..=100, 1);
+
+        assert_eq!(game.guess(10), GuessOutcome::OutOfAttempts);
+        assert_eq!(game.guess(37), GuessOutcome::OutOfAttempts);
+        assert_eq!(game.guess(500), GuessOutcome::OutOfAttempts);
+        assert_eq!(game.guesses(), 1);
+    }
+
+    #[test]
+    fn out_of_range_guesses_never_use_an_attempt() {
+        let mut game = Game::new(5, 1..=10, 1);
+
+        assert_eq!(game.guess(0), GuessOutcome::OutOfRange);
+        assert_eq!(game.guess(11), GuessOutcome::OutOfRange);
+        assert_eq!(game.guesses(), 0);
+        assert_eq!(game.guess(5), GuessOutcome::Correct);
+    }
+}
@@ -1,35 +1,695 @@
-use rand::Rng;
-use std::{cmp::Ordering, io};
+use rand::rngs::StdRng;
+use rand::{Rng, RngCore, SeedableRng};
+use std::{
+    cmp::Ordering,
+    io::{self, IsTerminal},
+    ops::RangeInclusive,
+    process,
+    str::FromStr,
+};
+
+mod game;
+mod scores;
+
+use game::{Game, GuessOutcome};
+use scores::{record_score, FileScoreStore};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl Difficulty {
+    fn parse(name: &str) -> Option<Difficulty> {
+        match name.to_lowercase().as_str() {
+            "easy" => Some(Difficulty::Easy),
+            "medium" => Some(Difficulty::Medium),
+            "hard" => Some(Difficulty::Hard),
+            _ => None,
+        }
+    }
+
+    /// The guessing range and the number of attempts allowed.
+    fn settings(&self) -> (RangeInclusive<u32>, u32) {
+        match self {
+            Difficulty::Easy => (1..=10, 5),
+            Difficulty::Medium => (1..=100, 10),
+            Difficulty::Hard => (1..=1000, 12),
+        }
+    }
+}
+
+struct Config {
+    min: u32,
+    max: u32,
+    max_attempts: u32,
+    hints: bool,
+    seed: Option<u64>,
+    /// The secret comes from a first player instead of the RNG.
+    two_player: bool,
+    /// Colour the feedback with ANSI escape codes.
+    color: bool,
+}
+
+impl Config {
+    /// Reads `--difficulty easy|medium|hard`, `--min N`, `--max N`,
+    /// `--max-attempts N`, `--hints`, `--seed N`, `--two-player` and
+    /// `--no-color`. The difficulty (medium unless given)
+    /// sets the range and attempts; `--min`, `--max` and `--max-attempts`
+    /// override it.
+    fn build(mut args: impl Iterator<Item = String>) -> Result<Config, String> {
+        let mut difficulty = Difficulty::Medium;
+        let mut min = None;
+        let mut max = None;
+        let mut max_attempts = None;
+        let mut hints = false;
+        let mut seed = None;
+        let mut two_player = false;
+        let mut color = true;
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--difficulty" => {
+                    let name = args.next().ok_or_else(|| {
+                        String::from("`--difficulty` expects easy, medium or hard")
+                    })?;
+                    difficulty = Difficulty::parse(&name).unwrap_or_else(|| {
+                        eprintln!("Unknown difficulty `{name}`, playing on medium");
+                        Difficulty::Medium
+                    });
+                }
+                "--min" => min = Some(number_after(&arg, &mut args)?),
+                "--max" => max = Some(number_after(&arg, &mut args)?),
+                "--max-attempts" => max_attempts = Some(number_after(&arg, &mut args)?),
+                "--hints" => hints = true,
+                "--seed" => seed = Some(number_after(&arg, &mut args)?),
+                "--two-player" => two_player = true,
+                "--no-color" => color = false,
+                _ => return Err(format!("unknown argument `{arg}`")),
+            }
+        }
+
+        let (range, attempts) = difficulty.settings();
+        let config = Config {
+            min: min.unwrap_or(*range.start()),
+            max: max.unwrap_or(*range.end()),
+            max_attempts: max_attempts.unwrap_or(attempts),
+            hints,
+            seed,
+            two_player,
+            color,
+        };
+
+        if config.min == 0 {
+            return Err(String::from("the range must start at 1 or above"));
+        }
+        if config.min >= config.max {
+            return Err(format!(
+                "--min ({}) must be less than --max ({})",
+                config.min, config.max
+            ));
+        }
+
+        if config.max_attempts == 0 {
+            return Err(String::from("--max-attempts must be at least 1"));
+        }
+
+        Ok(config)
+    }
+}
+
+/// Parses the value that follows `flag`.
+fn number_after<T: FromStr>(
+    flag: &str,
+    args: &mut impl Iterator<Item = String>,
+) -> Result<T, String> {
+    let value = args
+        .next()
+        .ok_or_else(|| format!("`{flag}` expects a number"))?;
+    value
+        .parse()
+        .map_err(|_| format!("`{value}` is not a positive number"))
+}
+
+#[derive(Debug, PartialEq)]
+enum Hint {
+    Warmer,
+    Colder,
+    Same,
+}
+
+/// Compares how far the previous and current guesses were from the secret.
+fn hint(previous_distance: u32, current_distance: u32) -> Hint {
+    match current_distance.cmp(&previous_distance) {
+        Ordering::Less => Hint::Warmer,
+        Ordering::Greater => Hint::Colder,
+        Ordering::Equal => Hint::Same,
+    }
+}
+
+fn win_message(guesses: u32) -> String {
+    if guesses == 1 {
+        String::from("You won in 1 guess!")
+    } else {
+        format!("You won in {guesses} guesses!")
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Color {
+    Red,
+    Green,
+    Blue,
+}
+
+/// `text` wrapped in the ANSI escape codes for `color`, or unchanged when
+/// colour is off.
+fn paint(text: &str, color: Color, enabled: bool) -> String {
+    if !enabled {
+        return text.to_string();
+    }
+
+    let code = match color {
+        Color::Red => 31,
+        Color::Green => 32,
+        Color::Blue => 34,
+    };
+    format!("\x1b[{code}m{text}\x1b[0m")
+}
 
 fn main() {
-    let num = io::stdout; 
+    let mut config = Config::build(std::env::args().skip(1)).unwrap_or_else(|err| {
+        eprintln!("Problem parsing arguments: {err}");
+        process::exit(1);
+    });
+    // https://no-color.org: any non-empty value turns colour off.
+    if std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty()) {
+        config.color = false;
+    }
+
+    // A seed makes every round's secret reproducible.
+    let mut rng: Box<dyn RngCore> = match config.seed {
+        Some(seed) => Box::new(StdRng::seed_from_u64(seed)),
+        None => Box::new(rand::thread_rng()),
+    };
+    let mut store = FileScoreStore::in_data_dir();
+    let mut stats = SessionStats::default();
 
-    let secret_number = rand::thread_rng().gen_range(1..=100);
+    loop {
+        match play_one_game(&config, &mut rng, read_line) {
+            Round::Won(guesses) => {
+                stats.record(guesses, true);
+                if let Some(store) = store.as_mut() {
+                    match record_score(store, guesses) {
+                        Ok(true) => println!("New record!"),
+                        Ok(false) => {}
+                        Err(err) => eprintln!("Couldn't update the high score: {err}"),
+                    }
+                }
+            }
+            Round::Lost(guesses) => stats.record(guesses, false),
+            Round::Quit => break,
+        }
+
+        println!("Play again? (y/n)");
+        match read_line() {
+            Some(answer) if wants_to_play_again(&answer) => continue,
+            _ => break,
+        }
+    }
+
+    if stats.games > 0 {
+        println!("{}", stats.summary());
+    }
+}
+
+/// Totals over every finished round in one run of the program.
+#[derive(Debug, Default, PartialEq)]
+struct SessionStats {
+    games: u32,
+    wins: u32,
+    total_guesses: u32,
+    /// Fewest guesses in a won game.
+    best: Option<u32>,
+    /// Most guesses in a won game.
+    worst: Option<u32>,
+}
+
+impl SessionStats {
+    fn record(&mut self, guesses: u32, won: bool) {
+        self.games += 1;
+        self.total_guesses += guesses;
+        if won {
+            self.wins += 1;
+            self.best = Some(self.best.map_or(guesses, |best| best.min(guesses)));
+            self.worst = Some(self.worst.map_or(guesses, |worst| worst.max(guesses)));
+        }
+    }
+
+    fn summary(&self) -> String {
+        let mut summary = format!(
+            "Games: {}, won: {}, total guesses: {}",
+            self.games, self.wins, self.total_guesses
+        );
+        if let (Some(best), Some(worst)) = (self.best, self.worst) {
+            summary.push_str(&format!(", best game: {best}, worst game: {worst}"));
+        }
+        summary
+    }
+}
+
+/// How a round ended.
+#[derive(Debug, PartialEq)]
+enum Round {
+    /// Won with this many guesses.
+    Won(u32),
+    /// Ran out of attempts or gave up after this many guesses.
+    Lost(u32),
+    /// Typed `quit` or closed the input.
+    Quit,
+}
+
+/// Words the player can type at the guess prompt instead of a number.
+#[derive(Debug, PartialEq)]
+enum Command {
+    /// Say whether the secret is even or odd.
+    Hint,
+    Quit,
+    /// Reveal the secret and end the round.
+    GiveUp,
+}
+
+fn parse_command(input: &str) -> Option<Command> {
+    match input.trim().to_lowercase().as_str() {
+        "hint" => Some(Command::Hint),
+        "quit" => Some(Command::Quit),
+        "giveup" => Some(Command::GiveUp),
+        _ => None,
+    }
+}
+
+/// Plays a single round with a fresh secret number, taking the guesses from
+/// `read`.
+fn play_one_game(
+    config: &Config,
+    rng: &mut dyn RngCore,
+    mut read: impl FnMut() -> Option<String>,
+) -> Round {
+    let secret_number = if config.two_player {
+        println!(
+            "Player one, choose a secret number between {} and {}.",
+            config.min, config.max
+        );
+        match read_secret(config, read_hidden_line) {
+            Some(secret) => secret,
+            None => return Round::Quit,
+        }
+    } else {
+        secret_number(rng, config)
+    };
 
     // println!("The secret number is: {}", secret_number);
 
+    let mut game = Game::new(secret_number, config.min..=config.max, config.max_attempts);
+    let mut previous_guess = None;
+
     loop {
-        println!("Please input your guess.");
+        println!("Please input your guess ({}-{}).", config.min, config.max);
 
-        let mut guess = String::new();
+        let Some(input) = read() else {
+            return Round::Quit;
+        };
 
-        io::stdin()
-            .read_line(&mut guess)
-            .expect("Failed to read line");
+        match parse_command(&input) {
+            Some(Command::Hint) => {
+                let parity = if game.secret().is_multiple_of(2) {
+                    "even"
+                } else {
+                    "odd"
+                };
+                println!("The number is {parity}");
+                continue;
+            }
+            Some(Command::Quit) => return Round::Quit,
+            Some(Command::GiveUp) => {
+                println!("The number was {}", game.secret());
+                return Round::Lost(game.guesses());
+            }
+            None => {}
+        }
 
-        let guess: u32 = match guess.trim().parse() {
-            Ok(num) => num,
-            Err(_) => continue,
+        // Only guesses that parse and are in range count towards the total.
+        let guess = match parse_guess(&input) {
+            Some(num) => num,
+            None => continue,
         };
 
-        println!("You guess : {}", guess);
-        match guess.cmp(&secret_number) {
-            Ordering::Less => println!("Too small"),
-            Ordering::Greater => println!("Too big"),
-            Ordering::Equal => {
-                println!("You Win");
-                break;
+        match game.guess(guess) {
+            GuessOutcome::OutOfRange => {
+                println!(
+                    "Out of range, guess between {} and {}",
+                    config.min, config.max
+                );
+                continue;
+            }
+            GuessOutcome::Correct => {
+                println!("You guess : {}", guess);
+                println!(
+                    "{}",
+                    paint(&win_message(game.guesses()), Color::Green, config.color)
+                );
+                return Round::Won(game.guesses());
+            }
+            GuessOutcome::OutOfAttempts => {
+                println!("You guess : {}", guess);
+                println!("You lose, the number was {}", game.secret());
+                return Round::Lost(game.guesses());
+            }
+            GuessOutcome::TooLow => {
+                println!("You guess : {}", guess);
+                println!("{}", paint("Too small", Color::Blue, config.color));
+            }
+            GuessOutcome::TooHigh => {
+                println!("You guess : {}", guess);
+                println!("{}", paint("Too big", Color::Red, config.color));
+            }
+        }
+
+        if config.hints {
+            if let Some(previous) = previous_guess {
+                match hint(
+                    game.secret().abs_diff(previous),
+                    game.secret().abs_diff(guess),
+                ) {
+                    Hint::Warmer => println!("Warmer"),
+                    Hint::Colder => println!("Colder"),
+                    Hint::Same => println!("Same as before"),
+                }
+            }
+        }
+        previous_guess = Some(guess);
+    }
+}
+
+/// The player's input as a number, or `None` if it isn't a positive whole
+/// number.
+fn parse_guess(input: &str) -> Option<u32> {
+    input.trim().parse().ok()
+}
+
+fn in_range(guess: u32, config: &Config) -> bool {
+    (config.min..=config.max).contains(&guess)
+}
+
+fn secret_number<R: Rng + ?Sized>(rng: &mut R, config: &Config) -> u32 {
+    rng.gen_range(config.min..=config.max)
+}
+
+/// Asks for the first player's secret with `read` until it's a number in
+/// range. `None` once input runs out.
+fn read_secret(config: &Config, mut read: impl FnMut() -> Option<String>) -> Option<u32> {
+    loop {
+        match validate_secret(&read()?, config) {
+            Ok(secret) => return Some(secret),
+            Err(err) => println!("{err}"),
+        }
+    }
+}
+
+fn validate_secret(input: &str, config: &Config) -> Result<u32, String> {
+    let secret = parse_guess(input).ok_or_else(|| format!("`{}` is not a number", input.trim()))?;
+    if !in_range(secret, config) {
+        return Err(format!(
+            "The secret must be between {} and {}",
+            config.min, config.max
+        ));
+    }
+    Ok(secret)
+}
+
+/// Like `read_line`, but without echoing what's typed when stdin is a
+/// terminal, so player two can't see the secret.
+fn read_hidden_line() -> Option<String> {
+    if !io::stdin().is_terminal() {
+        return read_line();
+    }
+    rpassword::read_password().ok()
+}
+
+/// One line from stdin, or `None` once input is closed.
+fn read_line() -> Option<String> {
+    let mut line = String::new();
+
+    let bytes = io::stdin()
+        .read_line(&mut line)
+        .expect("Failed to read line");
+
+    (bytes > 0).then_some(line)
+}
+
+fn wants_to_play_again(answer: &str) -> bool {
+    matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build(args: &[&str]) -> Result<Config, String> {
+        Config::build(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn build_reads_the_range_and_flags() {
+        let config = build(&["--min", "5", "--max", "50", "--hints", "--no-color"]).unwrap();
+
+        assert_eq!((config.min, config.max), (5, 50));
+        assert!(config.hints);
+        assert!(!config.color);
+        assert!(!config.two_player);
+        assert_eq!(config.seed, None);
+    }
+
+    #[test]
+    fn build_defaults_to_medium() {
+        let config = build(&[]).unwrap();
+
+        assert_eq!((config.min, config.max, config.max_attempts), (1, 100, 10));
+    }
+
+    #[test]
+    fn build_rejects_min_not_below_max() {
+        assert!(build(&["--min", "10", "--max", "10"]).is_err());
+        assert!(build(&["--min", "20", "--max", "10"]).is_err());
+    }
+
+    #[test]
+    fn build_rejects_a_range_starting_at_zero() {
+        assert!(build(&["--min", "0"]).is_err());
+    }
+
+    #[test]
+    fn build_rejects_bad_values_and_unknown_flags() {
+        assert!(build(&["--max"]).is_err());
+        assert!(build(&["--max", "-3"]).is_err());
+        assert!(build(&["--max-attempts", "0"]).is_err());
+        assert!(build(&["--colour"]).is_err());
+    }
+
+    #[test]
+    fn win_message_uses_singular_for_one_guess() {
+        assert_eq!(win_message(1), "You won in 1 guess!");
+        assert_eq!(win_message(4), "You won in 4 guesses!");
+    }
+
+    #[test]
+    fn invalid_entries_are_not_counted() {
+        // Seed 3 makes the secret 65.
+        let config = build(&["--seed", "3", "--max-attempts", "2"]).unwrap();
+        let mut rng = StdRng::seed_from_u64(3);
+        let mut input = ["abc\n", "\n", "500\n", "0\n", "hint\n", "50\n", "65\n"]
+            .into_iter()
+            .map(String::from);
+
+        assert_eq!(
+            play_one_game(&config, &mut rng, || input.next()),
+            Round::Won(2)
+        );
+    }
+
+    #[test]
+    fn hint_compares_distances() {
+        assert_eq!(hint(10, 3), Hint::Warmer);
+        assert_eq!(hint(3, 10), Hint::Colder);
+        assert_eq!(hint(5, 5), Hint::Same);
+    }
+
+    #[test]
+    fn each_difficulty_sets_its_range_and_attempts() {
+        assert_eq!(Difficulty::Easy.settings(), (1..=10, 5));
+        assert_eq!(Difficulty::Medium.settings(), (1..=100, 10));
+        assert_eq!(Difficulty::Hard.settings(), (1..=1000, 12));
+    }
+
+    #[test]
+    fn difficulty_overrides_come_from_the_flags() {
+        let config = build(&["--difficulty", "hard", "--max-attempts", "3"]).unwrap();
+
+        assert_eq!((config.min, config.max, config.max_attempts), (1, 1000, 3));
+    }
+
+    #[test]
+    fn seeded_secret_is_known_and_in_range() {
+        let config = build(&["--seed", "3"]).unwrap();
+        let mut rng = StdRng::seed_from_u64(config.seed.unwrap());
+        let secret = secret_number(&mut rng, &config);
+
+        assert_eq!(secret, 65);
+        assert!(in_range(secret, &config));
+    }
+
+    #[test]
+    fn in_range_includes_both_bounds() {
+        let config = build(&["--min", "5", "--max", "50"]).unwrap();
+
+        assert!(in_range(5, &config));
+        assert!(in_range(50, &config));
+        assert!(!in_range(4, &config));
+        assert!(!in_range(51, &config));
+    }
+
+    #[test]
+    fn parse_guess_reads_a_number() {
+        assert_eq!(parse_guess("42"), Some(42));
+    }
+
+    #[test]
+    fn parse_guess_ignores_surrounding_whitespace() {
+        assert_eq!(parse_guess("  42 \n"), Some(42));
+    }
+
+    #[test]
+    fn parse_guess_rejects_negative_numbers() {
+        assert_eq!(parse_guess("-5\n"), None);
+    }
+
+    #[test]
+    fn parse_guess_rejects_garbage() {
+        assert_eq!(parse_guess("forty-two\n"), None);
+        assert_eq!(parse_guess("4 2"), None);
+        assert_eq!(parse_guess(""), None);
+    }
+
+    #[test]
+    fn parse_command_knows_each_command() {
+        assert_eq!(parse_command("hint\n"), Some(Command::Hint));
+        assert_eq!(parse_command("quit\n"), Some(Command::Quit));
+        assert_eq!(parse_command("giveup\n"), Some(Command::GiveUp));
+    }
+
+    #[test]
+    fn parse_command_ignores_case_and_whitespace() {
+        assert_eq!(parse_command("  QUIT  \n"), Some(Command::Quit));
+        assert_eq!(parse_command("GiveUp"), Some(Command::GiveUp));
+    }
+
+    #[test]
+    fn numbers_and_other_words_are_not_commands() {
+        assert_eq!(parse_command("42\n"), None);
+        assert_eq!(parse_command("give up\n"), None);
+        assert_eq!(parse_command(""), None);
+    }
+
+    #[test]
+    fn validate_secret_accepts_a_number_in_range() {
+        let config = build(&["--min", "1", "--max", "10"]).unwrap();
+
+        assert_eq!(validate_secret(" 7\n", &config), Ok(7));
+    }
+
+    #[test]
+    fn validate_secret_rejects_junk_and_out_of_range() {
+        let config = build(&["--min", "1", "--max", "10"]).unwrap();
+
+        assert_eq!(
+            validate_secret("seven\n", &config),
+            Err(String::from("`seven` is not a number"))
+        );
+        assert_eq!(
+            validate_secret("11\n", &config),
+            Err(String::from("The secret must be between 1 and 10"))
+        );
+    }
+
+    #[test]
+    fn read_secret_asks_until_the_secret_is_valid() {
+        let config = build(&["--min", "1", "--max", "10"]).unwrap();
+        let mut input = ["seven\n", "11\n", "7\n", "3\n"]
+            .into_iter()
+            .map(String::from);
+
+        assert_eq!(read_secret(&config, || input.next()), Some(7));
+        assert_eq!(input.next().as_deref(), Some("3\n"));
+    }
+
+    #[test]
+    fn read_secret_gives_up_when_input_runs_out() {
+        let config = build(&[]).unwrap();
+        let mut input = ["nope\n"].into_iter().map(String::from);
+
+        assert_eq!(read_secret(&config, || input.next()), None);
+    }
+
+    #[test]
+    fn session_stats_aggregate_each_game() {
+        let mut stats = SessionStats::default();
+        for (guesses, won) in [(5, true), (10, false), (2, true), (8, true)] {
+            stats.record(guesses, won);
+        }
+
+        assert_eq!(
+            stats,
+            SessionStats {
+                games: 4,
+                wins: 3,
+                total_guesses: 25,
+                best: Some(2),
+                worst: Some(8),
             }
+        );
+        assert_eq!(
+            stats.summary(),
+            "Games: 4, won: 3, total guesses: 25, best game: 2, worst game: 8"
+        );
+    }
+
+    #[test]
+    fn session_stats_without_a_win_have_no_best_game() {
+        let mut stats = SessionStats::default();
+        stats.record(10, false);
+
+        assert_eq!(stats.summary(), "Games: 1, won: 0, total guesses: 10");
+    }
+
+    #[test]
+    fn paint_wraps_text_in_ansi_codes() {
+        assert_eq!(paint("Too big", Color::Red, true), "\x1b[31mToo big\x1b[0m");
+        assert_eq!(
+            paint("You won", Color::Green, true),
+            "\x1b[32mYou won\x1b[0m"
+        );
+        assert_eq!(
+            paint("Too small", Color::Blue, true),
+            "\x1b[34mToo small\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn paint_leaves_text_alone_without_colour() {
+        for color in [Color::Red, Color::Green, Color::Blue] {
+            assert_eq!(paint("Too big", color, false), "Too big");
         }
     }
 }
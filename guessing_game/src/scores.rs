@@ -0,0 +1,107 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+/// Where the fewest-guesses record is kept between runs.
+pub trait ScoreStore {
+    fn best(&self) -> io::Result<Option<u32>>;
+    fn save_best(&mut self, guesses: u32) -> io::Result<()>;
+}
+
+/// Stores the record as plain text in the user's data directory.
+pub struct FileScoreStore {
+    path: PathBuf,
+}
+
+impl FileScoreStore {
+    /// `$XDG_DATA_HOME/guessing_game/high_score.txt`, falling back to
+    /// `~/.local/share` and then `%APPDATA%`. `None` if none of them are set.
+    pub fn in_data_dir() -> Option<FileScoreStore> {
+        let data_dir = std::env::var_os("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .or_else(|| {
+                std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share"))
+            })
+            .or_else(|| std::env::var_os("APPDATA").map(PathBuf::from))?;
+
+        Some(FileScoreStore {
+            path: data_dir.join("guessing_game").join("high_score.txt"),
+        })
+    }
+}
+
+impl ScoreStore for FileScoreStore {
+    fn best(&self) -> io::Result<Option<u32>> {
+        match fs::read_to_string(&self.path) {
+            Ok(contents) => Ok(contents.trim().parse().ok()),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn save_best(&mut self, guesses: u32) -> io::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&self.path, format!("{guesses}\n"))
+    }
+}
+
+/// Saves `guesses` if it beats the stored record (or there is none yet) and
+/// returns whether it did.
+pub fn record_score(store: &mut dyn ScoreStore, guesses: u32) -> io::Result<bool> {
+    match store.best()? {
+        Some(best) if best <= guesses => Ok(false),
+        _ => {
+            store.save_best(guesses)?;
+            Ok(true)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Keeps the record in memory.
+    #[derive(Default)]
+    struct MemoryStore {
+        best: Option<u32>,
+    }
+
+    impl ScoreStore for MemoryStore {
+        fn best(&self) -> io::Result<Option<u32>> {
+            Ok(self.best)
+        }
+
+        fn save_best(&mut self, guesses: u32) -> io::Result<()> {
+            self.best = Some(guesses);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn first_score_is_a_record() {
+        let mut store = MemoryStore::default();
+
+        assert!(record_score(&mut store, 7).unwrap());
+        assert_eq!(store.best, Some(7));
+    }
+
+    #[test]
+    fn fewer_guesses_beat_the_record() {
+        let mut store = MemoryStore { best: Some(7) };
+
+        assert!(record_score(&mut store, 4).unwrap());
+        assert_eq!(store.best, Some(4));
+    }
+
+    #[test]
+    fn equal_or_more_guesses_keep_the_record() {
+        let mut store = MemoryStore { best: Some(7) };
+
+        assert!(!record_score(&mut store, 7).unwrap());
+        assert!(!record_score(&mut store, 9).unwrap());
+        assert_eq!(store.best, Some(7));
+    }
+}
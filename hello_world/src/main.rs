@@ -1,10 +1,56 @@
-#[derive(Default)]
+use std::fmt;
+
+#[derive(Debug, Default)]
 struct Person {
     first_name: String,
     last_name: String,
-}   
+}
+
+#[derive(Debug, PartialEq)]
+enum NameError {
+    Empty,
+    WhitespaceOnly,
+}
+
+impl fmt::Display for NameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NameError::Empty => write!(f, "name can't be empty"),
+            NameError::WhitespaceOnly => write!(f, "name can't be only whitespace"),
+        }
+    }
+}
+
+/// Trims `name`, rejecting it if nothing is left.
+fn validate_name(name: &str) -> Result<String, NameError> {
+    if name.is_empty() {
+        return Err(NameError::Empty);
+    }
+
+    let trimmed = name.trim();
+    if trimmed.is_empty() {
+        return Err(NameError::WhitespaceOnly);
+    }
+
+    Ok(trimmed.to_string())
+}
 
 impl Person {
+    fn new(first: impl Into<String>, last: impl Into<String>) -> Person {
+        Person {
+            first_name: first.into(),
+            last_name: last.into(),
+        }
+    }
+
+    /// Splits "Yusril Arzaqi" at the first whitespace. Everything after the
+    /// first word is the last name, so "Anna van der Berg" keeps "van der
+    /// Berg" together. `None` if there's only one word.
+    fn from_full_name(name: &str) -> Option<Person> {
+        let (first, last) = name.trim().split_once(char::is_whitespace)?;
+        Some(Person::new(first, last.trim_start()))
+    }
+
     fn first_name(&self) -> &str {
         &self.first_name
     }
@@ -19,6 +65,32 @@ impl Person {
     fn last_name_mut(&mut self) -> &mut String {
         &mut self.last_name
     }
+
+    fn set_first_name(&mut self, name: &str) -> Result<(), NameError> {
+        self.first_name = validate_name(name)?;
+        Ok(())
+    }
+
+    fn set_last_name(&mut self, name: &str) -> Result<(), NameError> {
+        self.last_name = validate_name(name)?;
+        Ok(())
+    }
+
+    /// "First Last", leaving out whichever part is empty.
+    fn full_name(&self) -> String {
+        [self.first_name.as_str(), self.last_name.as_str()]
+            .iter()
+            .filter(|part| !part.is_empty())
+            .copied()
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+impl fmt::Display for Person {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.full_name())
+    }
 }
 
 fn main() {
@@ -29,5 +101,96 @@ fn main() {
 
     println!("first name : {}", yusril.first_name());
     println!("last name : {}", yusril.last_name());
+    println!("person : {yusril}");
+
+    let mut adam = Person::new("Adam", "Saputra");
+    println!("full name : {}", adam.full_name());
+
+    if let Err(err) = adam.set_first_name("   ") {
+        println!("error : {err}");
+    }
+    adam.set_last_name(" Pratama ").expect("valid name");
+    println!("renamed : {adam}");
+
+    if let Some(anna) = Person::from_full_name("Anna van der Berg") {
+        println!("parsed : {} / {}", anna.first_name(), anna.last_name());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_keeps_both_parts() {
+        let person = Person::new("Adam", "Saputra");
+
+        assert_eq!(person.first_name(), "Adam");
+        assert_eq!(person.last_name(), "Saputra");
+        assert_eq!(person.full_name(), "Adam Saputra");
+    }
+
+    #[test]
+    fn full_name_leaves_out_empty_parts() {
+        assert_eq!(Person::new("Adam", "").full_name(), "Adam");
+        assert_eq!(Person::new("", "Saputra").full_name(), "Saputra");
+        assert_eq!(Person::new("", "").full_name(), "");
+        assert_eq!(Person::default().full_name(), "");
+    }
+
+    #[test]
+    fn display_is_the_full_name() {
+        let person = Person::new("Yusril", "Arzaqi");
+
+        assert_eq!(format!("{}", person), "Yusril Arzaqi");
+        assert_eq!(Person::new("Yusril", "").to_string(), "Yusril");
+    }
+
+    #[test]
+    fn validate_name_trims_a_valid_name() {
+        assert_eq!(validate_name("Adam"), Ok(String::from("Adam")));
+        assert_eq!(validate_name("  Adam \t"), Ok(String::from("Adam")));
+    }
+
+    #[test]
+    fn validate_name_rejects_empty_and_blank_names() {
+        assert_eq!(validate_name(""), Err(NameError::Empty));
+        assert_eq!(validate_name("  \t\n"), Err(NameError::WhitespaceOnly));
+    }
+
+    #[test]
+    fn setters_only_change_the_name_when_it_is_valid() {
+        let mut person = Person::new("Adam", "Saputra");
+
+        assert_eq!(person.set_first_name("   "), Err(NameError::WhitespaceOnly));
+        assert_eq!(person.set_last_name(""), Err(NameError::Empty));
+        assert_eq!(person.full_name(), "Adam Saputra");
+
+        assert_eq!(person.set_first_name(" Budi "), Ok(()));
+        assert_eq!(person.set_last_name("Pratama"), Ok(()));
+        assert_eq!(person.full_name(), "Budi Pratama");
+    }
 
+    #[test]
+    fn from_full_name_splits_at_the_space() {
+        let person = Person::from_full_name("Yusril Arzaqi").unwrap();
+
+        assert_eq!(person.first_name(), "Yusril");
+        assert_eq!(person.last_name(), "Arzaqi");
+    }
+
+    #[test]
+    fn from_full_name_keeps_a_multi_word_last_name() {
+        let person = Person::from_full_name("Anna van der Berg").unwrap();
+
+        assert_eq!(person.first_name(), "Anna");
+        assert_eq!(person.last_name(), "van der Berg");
+    }
+
+    #[test]
+    fn from_full_name_needs_two_words() {
+        assert!(Person::from_full_name("Yusril").is_none());
+        assert!(Person::from_full_name("  Yusril  ").is_none());
+        assert!(Person::from_full_name("").is_none());
+    }
 }
@@ -0,0 +1,212 @@
+use std::cell::Cell;
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Appetizer {
+    Soup,
+    Salad,
+}
+
+impl Appetizer {
+    /// Price in cents.
+    pub fn price(&self) -> u32 {
+        match self {
+            Appetizer::Soup => 450,
+            Appetizer::Salad => 575,
+        }
+    }
+}
+
+/// The customer picks the toast; the chef picks the fruit, so it can only
+/// be set through a constructor like `Breakfast::summer`.
+#[derive(Debug)]
+pub struct Breakfast {
+    pub toast: String,
+    seasonal_fruit: String,
+}
+
+impl Breakfast {
+    pub fn summer(toast: &str) -> Breakfast {
+        Breakfast {
+            toast: String::from(toast),
+            seasonal_fruit: String::from("peaches"),
+        }
+    }
+
+    pub fn seasonal_fruit(&self) -> &str {
+        &self.seasonal_fruit
+    }
+}
+
+/// Every appetizer the kitchen knows how to make. Enums can't list their
+/// own variants, so this has to be kept in step with `Appetizer` by hand.
+pub fn menu_items() -> Vec<Appetizer> {
+    vec![Appetizer::Soup, Appetizer::Salad]
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MainCourse {
+    Pasta,
+    Steak,
+    Curry,
+}
+
+/// A main course, with an appetizer if the customer wants one.
+#[derive(Debug, PartialEq, Eq)]
+pub struct FullOrder {
+    pub appetizer: Option<Appetizer>,
+    pub main_course: MainCourse,
+}
+
+/// Sum of the prices of `orders`, in cents.
+pub fn total(orders: &[Appetizer]) -> u32 {
+    orders.iter().map(Appetizer::price).sum()
+}
+
+/// The appetizers the kitchen can make today.
+pub struct Menu {
+    appetizers: Vec<Appetizer>,
+    next_order_id: Cell<u32>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Order {
+    pub id: u32,
+    pub appetizer: Appetizer,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum OrderError {
+    NotOnMenu(Appetizer),
+}
+
+impl fmt::Display for OrderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OrderError::NotOnMenu(item) => write!(f, "{item:?} is not on the menu"),
+        }
+    }
+}
+
+impl Error for OrderError {}
+
+impl Menu {
+    pub fn new(appetizers: Vec<Appetizer>) -> Menu {
+        Menu {
+            appetizers,
+            next_order_id: Cell::new(1),
+        }
+    }
+
+    /// Orders `item` if it's on the menu. Orders are numbered from 1.
+    pub fn place_order(&self, item: Appetizer) -> Result<Order, OrderError> {
+        if !self.appetizers.contains(&item) {
+            return Err(OrderError::NotOnMenu(item));
+        }
+
+        let id = self.next_order_id.get();
+        self.next_order_id.set(id + 1);
+
+        Ok(Order {
+            id,
+            appetizer: item,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn place_order_numbers_orders_from_one() {
+        let menu = Menu::new(vec![Appetizer::Soup, Appetizer::Salad]);
+
+        assert_eq!(
+            menu.place_order(Appetizer::Salad),
+            Ok(Order {
+                id: 1,
+                appetizer: Appetizer::Salad,
+            })
+        );
+        assert_eq!(
+            menu.place_order(Appetizer::Soup).map(|order| order.id),
+            Ok(2)
+        );
+    }
+
+    #[test]
+    fn place_order_rejects_items_not_on_the_menu() {
+        let menu = Menu::new(vec![Appetizer::Soup]);
+
+        assert_eq!(
+            menu.place_order(Appetizer::Salad),
+            Err(OrderError::NotOnMenu(Appetizer::Salad))
+        );
+        // A refused order doesn't use up a number.
+        assert_eq!(
+            menu.place_order(Appetizer::Soup).map(|order| order.id),
+            Ok(1)
+        );
+    }
+
+    #[test]
+    fn each_appetizer_has_a_price() {
+        assert_eq!(Appetizer::Soup.price(), 450);
+        assert_eq!(Appetizer::Salad.price(), 575);
+    }
+
+    #[test]
+    fn total_adds_up_every_item() {
+        assert_eq!(
+            total(&[Appetizer::Soup, Appetizer::Salad, Appetizer::Soup]),
+            1475
+        );
+        assert_eq!(total(&[]), 0);
+    }
+
+    #[test]
+    fn full_order_with_an_appetizer() {
+        let dinner = FullOrder {
+            appetizer: Some(Appetizer::Salad),
+            main_course: MainCourse::Steak,
+        };
+
+        assert_eq!(dinner.appetizer, Some(Appetizer::Salad));
+        assert_eq!(dinner.main_course, MainCourse::Steak);
+    }
+
+    #[test]
+    fn full_order_without_an_appetizer() {
+        let lunch = FullOrder {
+            appetizer: None,
+            main_course: MainCourse::Curry,
+        };
+
+        assert_eq!(lunch.appetizer, None);
+        assert_ne!(
+            lunch,
+            FullOrder {
+                appetizer: Some(Appetizer::Soup),
+                main_course: MainCourse::Curry,
+            }
+        );
+    }
+
+    #[test]
+    fn menu_items_lists_every_appetizer_once() {
+        assert_eq!(menu_items(), [Appetizer::Soup, Appetizer::Salad]);
+    }
+
+    #[test]
+    fn summer_breakfast_comes_with_peaches() {
+        let mut meal = Breakfast::summer("Rye");
+        assert_eq!(meal.toast, "Rye");
+        assert_eq!(meal.seasonal_fruit(), "peaches");
+
+        // Changing the toast leaves the chef's fruit alone.
+        meal.toast = String::from("Wheat");
+        assert_eq!(meal.seasonal_fruit(), "peaches");
+    }
+}
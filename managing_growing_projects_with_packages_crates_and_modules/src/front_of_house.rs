@@ -1 +1,2 @@
 pub mod hosting;
+pub mod serving;
@@ -0,0 +1,3 @@
+pub fn take_order() {
+    println!("Took the order");
+}
@@ -1,7 +1,193 @@
+pub mod back_of_house;
 mod front_of_house;
 
+// Re-exported so callers can write `Appetizer` instead of
+// `back_of_house::Appetizer`; the longer path still works too.
+pub use crate::back_of_house::Appetizer;
 pub use crate::front_of_house::hosting;
 
+use std::error::Error;
+use std::fmt;
+
+use crate::back_of_house::{menu_items, total, Breakfast, FullOrder, MainCourse, Menu, OrderError};
+
+/// A restaurant that only takes orders while it's open.
+pub struct Restaurant {
+    open: bool,
+    menu: Menu,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum RestaurantError {
+    Closed,
+    Order(OrderError),
+}
+
+impl fmt::Display for RestaurantError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RestaurantError::Closed => write!(f, "the restaurant is closed"),
+            RestaurantError::Order(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl Error for RestaurantError {}
+
+impl From<OrderError> for RestaurantError {
+    fn from(err: OrderError) -> Self {
+        RestaurantError::Order(err)
+    }
+}
+
+impl Restaurant {
+    /// A new restaurant starts out closed.
+    pub fn new(menu: Menu) -> Restaurant {
+        Restaurant { open: false, menu }
+    }
+
+    pub fn open(&mut self) {
+        self.open = true;
+    }
+
+    pub fn close(&mut self) {
+        self.open = false;
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    pub fn serve(&self, item: Appetizer) -> Result<(), RestaurantError> {
+        if !self.open {
+            return Err(RestaurantError::Closed);
+        }
+
+        let order = self.menu.place_order(item)?;
+        println!("Order #{}: {:?}", order.id, order.appetizer);
+        Ok(())
+    }
+}
+
 pub fn eat_at_restaurant() {
+    // Absolute path
+    crate::front_of_house::hosting::add_to_waitlist();
+
+    // Relative path, through the `hosting` re-export above
     hosting::add_to_waitlist();
+
+    // Relative path from the crate root
+    front_of_house::serving::take_order();
+
+    println!("--- Menu ---");
+    for item in menu_items() {
+        println!("{:<8}{:>8}", format!("{item:?}"), dollars(item.price()));
+    }
+
+    let mut restaurant = Restaurant::new(Menu::new(vec![Appetizer::Soup]));
+    restaurant.open();
+
+    let mut served = Vec::new();
+    for item in [Appetizer::Soup, Appetizer::Salad, Appetizer::Soup] {
+        match restaurant.serve(item) {
+            Ok(()) => served.push(item),
+            Err(err) => println!("Sorry, {err}"),
+        }
+    }
+
+    restaurant.close();
+    if let Err(err) = restaurant.serve(Appetizer::Soup) {
+        println!("Sorry, {err}");
+    }
+
+    println!("--- Bill ---");
+    for item in &served {
+        println!("{:<8}{:>8}", format!("{item:?}"), dollars(item.price()));
+    }
+    println!("{:<8}{:>8}", "Total", dollars(total(&served)));
+
+    let dinner = FullOrder {
+        appetizer: Some(Appetizer::Salad),
+        main_course: MainCourse::Steak,
+    };
+    let lunch = FullOrder {
+        appetizer: None,
+        main_course: MainCourse::Pasta,
+    };
+    println!("{dinner:?}");
+    println!("{lunch:?}");
+
+    // Order a breakfast in the summer with Rye toast
+    let mut meal = Breakfast::summer("Rye");
+    // Change our mind about what bread we'd like
+    meal.toast = String::from("Wheat");
+    println!("I'd like {} toast please", meal.toast);
+
+    // The next line won't compile if we uncomment it; we're not allowed
+    // to see or modify the seasonal fruit that comes with the meal
+    // meal.seasonal_fruit = String::from("blueberries");
+    println!("It comes with {}", meal.seasonal_fruit());
+}
+
+fn dollars(cents: u32) -> String {
+    format!("${}.{:02}", cents / 100, cents % 100)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn open_restaurant_serves_what_is_on_the_menu() {
+        let mut restaurant = Restaurant::new(Menu::new(vec![Appetizer::Soup]));
+        restaurant.open();
+
+        assert_eq!(restaurant.serve(Appetizer::Soup), Ok(()));
+        assert_eq!(
+            restaurant.serve(Appetizer::Salad),
+            Err(RestaurantError::Order(OrderError::NotOnMenu(
+                Appetizer::Salad
+            )))
+        );
+    }
+
+    #[test]
+    fn closed_restaurant_serves_nothing() {
+        let mut restaurant = Restaurant::new(Menu::new(vec![Appetizer::Soup]));
+        assert!(!restaurant.is_open());
+        assert_eq!(
+            restaurant.serve(Appetizer::Soup),
+            Err(RestaurantError::Closed)
+        );
+
+        restaurant.open();
+        restaurant.close();
+        assert_eq!(
+            restaurant.serve(Appetizer::Soup),
+            Err(RestaurantError::Closed)
+        );
+    }
+
+    #[test]
+    fn reexported_appetizer_is_the_back_of_house_type() {
+        use std::any::TypeId;
+
+        let soup: back_of_house::Appetizer = crate::Appetizer::Soup;
+
+        assert_eq!(soup, back_of_house::Appetizer::Soup);
+        assert_eq!(
+            TypeId::of::<crate::Appetizer>(),
+            TypeId::of::<back_of_house::Appetizer>()
+        );
+    }
+
+    #[test]
+    fn hosting_is_reachable_through_the_reexport() {
+        // Both paths name the same public function.
+        let reexported: fn() = hosting::add_to_waitlist;
+        let nested: fn() = front_of_house::hosting::add_to_waitlist;
+
+        assert!(std::ptr::fn_addr_eq(reexported, nested));
+        reexported();
+    }
 }
@@ -1,11 +1,21 @@
 #![allow(dead_code)]
 
 fn main() {
-    // let mut s1 = String::from("hello");
-    // let len = calculate_length(&s1);
-    // println!("The length of '{}' is {}", s1, len);
+    let mut s1 = String::from("hello");
+    let len = calculate_length(&s1);
+    println!("The length of '{}' is {}", s1, len);
 
-    // change(&mut s1); // create mutable reference
+    append_world(&mut s1); // create mutable reference
+    println!("after append_world: {}", s1);
+
+    let mut a = String::from("left");
+    let mut b = String::from("right");
+    swap_strings(&mut a, &mut b); // two mutable references to different strings
+    println!("a: {}, b: {}", a, b);
+
+    let sentence = String::from("hello world");
+    let word = first_word(&sentence); // borrows a slice of `sentence`
+    println!("first word: {}", word);
 
     let s = String::from("hello");
 
@@ -15,15 +25,31 @@ fn main() {
     println!("r1: {}, r2: {}", r1, r2);
 }
 
-fn change(s: &mut String) {
+fn append_world(s: &mut String) {
     s.push_str(", world"); // cannot mutate bororwed variable
 }
 
+fn swap_strings(a: &mut String, b: &mut String) {
+    std::mem::swap(a, b); // nothing is moved out, only the contents change places
+}
+
+fn first_word(s: &str) -> &str {
+    let bytes = s.as_bytes();
+
+    for (i, &item) in bytes.iter().enumerate() {
+        if item == b' ' {
+            return &s[0..i];
+        }
+    }
+
+    s // no space, the whole string is one word
+}
+
 fn some_func(s: &String) -> (&String, String) {
     (s, s.to_owned())
 }
 
-fn calculate_length(some_string: &String) -> usize {
+fn calculate_length(some_string: &str) -> usize {
     some_string.len()
 }
 
@@ -82,3 +108,46 @@ fn borrowing() {
     let s2 = s1.clone();
     println!("s1: {}\ns2: {}", s1, s2);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn append_world_appends_in_place() {
+        let mut s = String::from("hello");
+        append_world(&mut s);
+
+        assert_eq!(s, "hello, world");
+    }
+
+    #[test]
+    fn swap_strings_exchanges_contents() {
+        let mut a = String::from("left");
+        let mut b = String::from("right");
+        swap_strings(&mut a, &mut b);
+
+        assert_eq!(a, "right");
+        assert_eq!(b, "left");
+    }
+
+    #[test]
+    fn first_word_stops_at_the_first_space() {
+        assert_eq!(first_word("hello wide world"), "hello");
+    }
+
+    #[test]
+    fn first_word_of_one_word_is_the_whole_string() {
+        assert_eq!(first_word("hello"), "hello");
+    }
+
+    #[test]
+    fn first_word_of_nothing_is_empty() {
+        assert_eq!(first_word(""), "");
+    }
+
+    #[test]
+    fn first_word_after_a_leading_space_is_empty() {
+        assert_eq!(first_word(" hello"), "");
+    }
+}
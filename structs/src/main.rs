@@ -8,13 +8,39 @@ impl Rectangle {
     fn area(&self) -> u32 {
         self.width * self.height
     }
+
+    fn perimeter(&self) -> u32 {
+        2 * (self.width + self.height)
+    }
+
+    fn is_square(&self) -> bool {
+        self.width == self.height
+    }
+
+    /// A new rectangle `factor` times bigger, or `None` if a side would
+    /// overflow `u32`.
+    fn checked_scale(&self, factor: u32) -> Option<Rectangle> {
+        Some(Rectangle {
+            width: self.width.checked_mul(factor)?,
+            height: self.height.checked_mul(factor)?,
+        })
+    }
+
+    fn scale(&self, factor: u32) -> Rectangle {
+        self.checked_scale(factor)
+            .expect("scaled rectangle is too big for u32")
+    }
+
+    fn scaled_mut(&mut self, factor: u32) {
+        *self = self.scale(factor);
+    }
 }
 
 impl Rectangle {
     fn can_hold(&self, other: &Rectangle) -> bool {
         self.width > other.width && self.height > other.height
     }
-    
+
     fn square(size: u32) -> Self {
         Self {
             width: size,
@@ -37,10 +63,103 @@ fn main() {
         height: 45,
     };
 
+    println!(
+        "The area of the rectangle is {} square pixels.",
+        rect1.area()
+    );
+
+    println!("The perimeter of rect1 is {}.", rect1.perimeter());
+
     println!("Can rect1 hold rect2? {}", rect1.can_hold(&rect2));
     println!("Can rect1 hold rect3? {}", rect1.can_hold(&rect3));
 
     let sq = Rectangle::square(50u32);
 
     println!("square : {:#?}", sq);
+    println!("Is sq a square? {}", sq.is_square());
+    println!("Is rect1 a square? {}", rect1.is_square());
+
+    println!("rect2 scaled by 2 : {:?}", rect2.scale(2));
+    println!(
+        "rect3 scaled by u32::MAX : {:?}",
+        rect3.checked_scale(u32::MAX)
+    );
+
+    let mut grown = Rectangle::square(3);
+    grown.scaled_mut(10);
+    println!("grown : {:?}", grown);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(width: u32, height: u32) -> Rectangle {
+        Rectangle { width, height }
+    }
+
+    #[test]
+    fn area_of_a_square() {
+        assert_eq!(rect(7, 7).area(), 49);
+    }
+
+    #[test]
+    fn area_of_a_rectangle() {
+        assert_eq!(rect(30, 50).area(), 1500);
+    }
+
+    #[test]
+    fn larger_rectangle_can_hold_a_smaller_one() {
+        assert!(rect(8, 7).can_hold(&rect(5, 1)));
+    }
+
+    #[test]
+    fn cannot_hold_a_wider_rectangle() {
+        assert!(!rect(8, 7).can_hold(&rect(9, 1)));
+    }
+
+    #[test]
+    fn cannot_hold_a_rectangle_of_the_same_size() {
+        assert!(!rect(8, 7).can_hold(&rect(8, 7)));
+    }
+
+    #[test]
+    fn square_has_equal_sides() {
+        let square = Rectangle::square(5);
+
+        assert_eq!((square.width, square.height), (5, 5));
+        assert_eq!(square.area(), 25);
+    }
+
+    #[test]
+    fn perimeter_adds_all_four_sides() {
+        assert_eq!(rect(30, 50).perimeter(), 160);
+    }
+
+    #[test]
+    fn is_square_only_with_equal_sides() {
+        assert!(rect(5, 5).is_square());
+        assert!(!rect(30, 50).is_square());
+    }
+
+    #[test]
+    fn scale_multiplies_both_sides() {
+        let scaled = rect(3, 4).scale(3);
+
+        assert_eq!((scaled.width, scaled.height), (9, 12));
+    }
+
+    #[test]
+    fn scaled_mut_scales_in_place() {
+        let mut rectangle = rect(3, 4);
+        rectangle.scaled_mut(2);
+
+        assert_eq!((rectangle.width, rectangle.height), (6, 8));
+    }
+
+    #[test]
+    fn checked_scale_catches_overflow() {
+        assert!(rect(3, 4).checked_scale(u32::MAX).is_none());
+        assert!(rect(1, 1).checked_scale(u32::MAX).is_some());
+    }
 }